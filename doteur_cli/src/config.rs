@@ -0,0 +1,310 @@
+// Copyright ⓒ 2021-2024 LABEYE Loïc
+// This tool is distributed under the MIT License, check out [here](https://github.com/nag763/doteur/blob/main/LICENCE.MD).
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::args::STANDARD_STREAM;
+use crate::DoteurCliError;
+
+/// Name of the configuration file looked after in the working directory
+pub const CONFIG_FILE: &str = "doteur.toml";
+
+/// The sources of the input
+#[derive(Deserialize, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Sources {
+    /// The sql files or directories, the file names can contain wildcards
+    pub input: Option<Vec<String>>,
+    /// The SQL dialect of the input
+    pub dialect: Option<String>,
+}
+
+/// The filters applied on the tables and their columns
+#[derive(Deserialize, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Filters {
+    /// The included tables
+    pub include: Option<Vec<String>>,
+    /// The excluded tables
+    pub exclude: Option<Vec<String>>,
+    /// The excluded columns
+    pub exclude_columns: Option<Vec<String>>,
+    /// The focused table
+    pub focus: Option<String>,
+    /// The maximal number of relations to the focused table
+    pub depth: Option<usize>,
+    /// Whether the excluded tables are drawn as stubs
+    pub stubs: Option<bool>,
+}
+
+/// The appearance of the outputs
+#[derive(Deserialize, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// Whether the outputs are rendered in dark mode
+    pub dark_mode: Option<bool>,
+    /// Whether a legend is added to the outputs
+    pub legend: Option<bool>,
+    /// Whether the relations are drawn with the crow's foot notation
+    pub crows_foot: Option<bool>,
+    /// How much of the definition of the columns is shown
+    pub verbosity: Option<String>,
+    /// Whether the svg outputs are rendered without graphviz
+    pub native: Option<bool>,
+}
+
+/// A value that can be given once or as a list
+#[derive(Deserialize, Clone)]
+#[serde(untagged)]
+pub enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl From<OneOrMany> for Vec<String> {
+    fn from(value: OneOrMany) -> Vec<String> {
+        match value {
+            OneOrMany::One(value) => vec![value],
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
+/// The outputs to write
+#[derive(Deserialize, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Outputs {
+    /// The output file or files
+    pub output: Option<OneOrMany>,
+    /// The format of the output files, inferred from their extension if none is given
+    pub format: Option<String>,
+    /// The formats to render next to the first output file
+    pub formats: Option<Vec<String>>,
+    /// Whether the parts of the input that couldn't be understood are errors
+    pub strict: Option<bool>,
+}
+
+/// The settings of a profile, overriding the ones of the configuration file
+#[derive(Deserialize, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    /// The sources of the input
+    pub sources: Sources,
+    /// The filters applied on the tables and their columns
+    pub filters: Filters,
+    /// The appearance of the outputs
+    pub theme: Theme,
+    /// The outputs to write
+    pub outputs: Outputs,
+}
+
+/// The content of a configuration file
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The default sources of the input
+    sources: Sources,
+    /// The default filters
+    filters: Filters,
+    /// The default appearance of the outputs
+    theme: Theme,
+    /// The default outputs
+    outputs: Outputs,
+    /// The named profiles
+    profile: HashMap<String, Profile>,
+    /// The directory of the configuration file, the paths it holds being relative to it
+    #[serde(skip)]
+    directory: PathBuf,
+}
+
+impl Config {
+    /// Reads the configuration file at the given location
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The location of the configuration file
+    pub fn from_file(path: &str) -> Result<Config, DoteurCliError> {
+        let content: String = fs::read_to_string(path).map_err(|e| {
+            DoteurCliError::bad_config(&format!("The file {} couldn't be read : {}", path, e))
+        })?;
+        let mut config: Config = toml::from_str(&content)
+            .map_err(|e| DoteurCliError::bad_config(&format!("The file {} : {}", path, e)))?;
+        config.directory = Path::new(path)
+            .parent()
+            .map_or_else(PathBuf::new, Path::to_path_buf);
+        Ok(config)
+    }
+
+    /// Returns the settings of the configuration file, overriden by the ones of the given
+    /// profile if any
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The name of the profile to apply
+    pub fn get_settings(&self, profile: Option<&str>) -> Result<Profile, DoteurCliError> {
+        let defaults: Profile = Profile {
+            sources: self.sources.clone(),
+            filters: self.filters.clone(),
+            theme: self.theme.clone(),
+            outputs: self.outputs.clone(),
+        };
+        let settings: Profile = match profile {
+            Some(name) => match self.profile.get(name) {
+                Some(profile) => defaults.merge(profile.clone()),
+                None => {
+                    return Err(DoteurCliError::bad_config(&format!(
+                        "The profile {} isn't declared",
+                        name
+                    )))
+                }
+            },
+            None => defaults,
+        };
+        Ok(settings.resolve_paths(&self.directory))
+    }
+}
+
+/// Returns the path relative to the given directory
+///
+/// The absolute paths, the standard streams and the URLs are returned as they are.
+///
+/// # Arguments
+///
+/// * `directory` - The directory the path is relative to
+/// * `path` - The path to resolve
+fn resolve_path(directory: &Path, path: String) -> String {
+    if path == STANDARD_STREAM || path.contains("://") || Path::new(&path).is_absolute() {
+        path
+    } else {
+        directory.join(path).display().to_string()
+    }
+}
+
+impl Profile {
+    /// Resolves the input and output paths relative to the given directory
+    fn resolve_paths(mut self, directory: &Path) -> Profile {
+        self.sources.input = self.sources.input.map(|inputs| {
+            inputs
+                .into_iter()
+                .map(|input| resolve_path(directory, input))
+                .collect()
+        });
+        self.outputs.output = self.outputs.output.map(|output| {
+            OneOrMany::Many(
+                Vec::from(output)
+                    .into_iter()
+                    .map(|output| resolve_path(directory, output))
+                    .collect(),
+            )
+        });
+        self
+    }
+
+    /// Overrides the settings with the ones declared in the given profile
+    fn merge(self, other: Profile) -> Profile {
+        Profile {
+            sources: Sources {
+                input: other.sources.input.or(self.sources.input),
+                dialect: other.sources.dialect.or(self.sources.dialect),
+            },
+            filters: Filters {
+                include: other.filters.include.or(self.filters.include),
+                exclude: other.filters.exclude.or(self.filters.exclude),
+                exclude_columns: other
+                    .filters
+                    .exclude_columns
+                    .or(self.filters.exclude_columns),
+                focus: other.filters.focus.or(self.filters.focus),
+                depth: other.filters.depth.or(self.filters.depth),
+                stubs: other.filters.stubs.or(self.filters.stubs),
+            },
+            theme: Theme {
+                dark_mode: other.theme.dark_mode.or(self.theme.dark_mode),
+                legend: other.theme.legend.or(self.theme.legend),
+                crows_foot: other.theme.crows_foot.or(self.theme.crows_foot),
+                verbosity: other.theme.verbosity.or(self.theme.verbosity),
+                native: other.theme.native.or(self.theme.native),
+            },
+            outputs: Outputs {
+                output: other.outputs.output.or(self.outputs.output),
+                format: other.outputs.format.or(self.outputs.format),
+                formats: other.outputs.formats.or(self.outputs.formats),
+                strict: other.outputs.strict.or(self.outputs.strict),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const CONFIG: &str = r#"
+[sources]
+input = ["schema/*.sql", "/tmp/other.sql"]
+
+[theme]
+dark_mode = true
+legend = true
+
+[outputs]
+output = "docs/schema.svg"
+
+[profile.billing.theme]
+dark_mode = false
+
+[profile.billing.outputs]
+output = ["docs/billing.png", "-"]
+"#;
+
+    #[test]
+    fn test_get_settings() {
+        let config: Config = toml::from_str(CONFIG).unwrap();
+        let settings: Profile = config.get_settings(None).unwrap();
+        assert_eq!(settings.theme.dark_mode, Some(true), "default");
+        assert_eq!(settings.theme.legend, Some(true), "default");
+
+        let settings: Profile = config.get_settings(Some("billing")).unwrap();
+        assert_eq!(
+            settings.theme.dark_mode,
+            Some(false),
+            "profile over default"
+        );
+        assert_eq!(settings.theme.legend, Some(true), "default kept");
+        assert_eq!(
+            settings.outputs.output.map(Vec::from),
+            Some(vec![String::from("docs/billing.png"), String::from("-")]),
+            "profile over default"
+        );
+
+        assert!(config.get_settings(Some("foo")).is_err(), "unknown profile");
+    }
+
+    #[test]
+    fn test_resolve_paths() {
+        let mut config: Config = toml::from_str(CONFIG).unwrap();
+        config.directory = PathBuf::from("other/dir");
+        let settings: Profile = config.get_settings(Some("billing")).unwrap();
+        assert_eq!(
+            settings.sources.input,
+            Some(vec![
+                String::from("other/dir/schema/*.sql"),
+                String::from("/tmp/other.sql")
+            ]),
+            "inputs relative to the configuration file"
+        );
+        assert_eq!(
+            settings.outputs.output.map(Vec::from),
+            Some(vec![
+                String::from("other/dir/docs/billing.png"),
+                String::from("-")
+            ]),
+            "outputs relative to the configuration file"
+        );
+    }
+}
@@ -11,6 +11,10 @@ enum DoteurCliErrorType {
     NoTableFound,
     NoInput,
     BadInput,
+    NotUnderstood,
+    BadConfig,
+    OutputsFailed,
+    GraphvizFailed,
 }
 
 #[derive(Debug)]
@@ -26,7 +30,11 @@ impl fmt::Display for DoteurCliError {
             DoteurCliErrorType::ExtensionNotSupported => format!("The given extension isn't supported. Please verify it is one of the following :\n\n{}", self.message.as_ref().unwrap()),
             DoteurCliErrorType::NoTableFound => "No table found for the given input".to_string(),
             DoteurCliErrorType::NoInput => "Please precise at least one argument as input".to_string(),
-            DoteurCliErrorType::BadInput => self.message.as_ref().unwrap().to_string()
+            DoteurCliErrorType::BadInput => self.message.as_ref().unwrap().to_string(),
+            DoteurCliErrorType::NotUnderstood => format!("{} part(s) of the input couldn't be understood, the output hasn't been written as the strict mode is enabled", self.message.as_ref().unwrap()),
+            DoteurCliErrorType::BadConfig => format!("The configuration couldn't be applied : {}", self.message.as_ref().unwrap()),
+            DoteurCliErrorType::OutputsFailed => format!("{} output(s) couldn't be written", self.message.as_ref().unwrap()),
+            DoteurCliErrorType::GraphvizFailed => format!("Graphviz couldn't render the output :\n{}", self.message.as_ref().unwrap())
        };
         write!(f, "{}", err_msg)
     }
@@ -61,6 +69,13 @@ impl DoteurCliError {
             r#type: DoteurCliErrorType::NoInput,
         }
     }
+    pub fn not_understood(count: usize) -> DoteurCliError {
+        DoteurCliError {
+            message: Some(count.to_string()),
+            r#type: DoteurCliErrorType::NotUnderstood,
+        }
+    }
+
     // Allow as it depends from the feature it's compiled with
     #[allow(dead_code)]
     pub fn bad_input(message: &str) -> DoteurCliError {
@@ -69,4 +84,25 @@ impl DoteurCliError {
             r#type: DoteurCliErrorType::BadInput,
         }
     }
+
+    pub fn bad_config(message: &str) -> DoteurCliError {
+        DoteurCliError {
+            message: Some(message.to_string()),
+            r#type: DoteurCliErrorType::BadConfig,
+        }
+    }
+
+    pub fn outputs_failed(count: usize) -> DoteurCliError {
+        DoteurCliError {
+            message: Some(count.to_string()),
+            r#type: DoteurCliErrorType::OutputsFailed,
+        }
+    }
+
+    pub fn graphviz_failed(message: &str) -> DoteurCliError {
+        DoteurCliError {
+            message: Some(message.to_string()),
+            r#type: DoteurCliErrorType::GraphvizFailed,
+        }
+    }
 }
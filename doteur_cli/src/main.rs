@@ -8,7 +8,7 @@
 //! <u>Warning :</u> It is highly recommended to install <a href="https://graphviz.org/download/">Graphviz</a> prior using this tool
 //! <p>Doteur is a CLI (Command Line Interface) tool that has for purpose to render the SQL schemas into good looking graphs. This will help you to easily understand the structure of a large database and understand what happens behind the scenes of your project.</p>
 //! Besides, you will be able to use the large panel of features to either sort the tables you want to visualize or render with a different color scheme for instance.
-//! So far the tool handles both the MySQL and SQLite syntaxes, but it is planned to handle the Postgre one as soon as the formers will be considered as stable. The input of the tool can be either a sql file export, or given the version you downloaded, connect to either a MySQL running instance or an existing SQLite database.
+//! So far the tool handles the MySQL, SQLite and PostgreSQL syntaxes, including the exports made with `mysqldump` and `pg_dump`. The input of the tool can be either a sql file export, or given the version you downloaded, connect to either a MySQL running instance or an existing SQLite database.
 //! The tool has been developed on Linux, but is also available for Windows 10 and 11 and macOS.
 //! <br/>
 //! <p>Useful links :</p>
@@ -23,20 +23,31 @@
 /// This module mainly use clap derive in order to get the user input
 /// and use them in order to render
 mod args;
+/// Module used to read the configuration file
+///
+/// The configuration file holds default values for the CLI args, grouped in named profiles
+/// or not
+mod config;
 /// Module used to handle common errors
 mod errors;
 
-use std::env;
-use std::process::Command;
+use std::cell::OnceCell;
+use std::io::{self, Write};
+use std::process::{Child, ChildStdin, Command, Output, Stdio};
+use std::thread;
 use which::which;
 
-use crate::args::{Args, POSSIBLE_DOTS_OUTPUT};
+use crate::args::{can_render_with_graphviz, Args, POSSIBLE_DOTS_OUTPUT, STANDARD_STREAM};
 use crate::errors::DoteurCliError;
 
+#[cfg(feature = "native_svg")]
+use doteur_core::render_svg;
+use doteur_core::schema::Schema;
 use doteur_core::tools::write_output_to_file;
-use doteur_core::{contains_sql_tables, process_data};
-
-use clap::Parser;
+use doteur_core::{
+    contains_sql_tables, parse_schema, parse_schema_with_dialect, render_dbml, render_dot,
+    render_json, render_mermaid, render_plantuml,
+};
 
 #[macro_use]
 extern crate cfg_if;
@@ -57,54 +68,233 @@ fn main() {
     });
 }
 
+/// Returns the source and the line within it of a line of the sources joined together
+///
+/// # Arguments
+///
+/// * `sources` - The name and the content of each source, in the order they have been joined
+/// * `line` - The line within the joined sources, starting from 1
+fn locate_line(sources: &[(String, String)], line: usize) -> (&str, usize) {
+    let mut first_line: usize = 1;
+    for (name, content) in sources {
+        // The sources are joined with a line break
+        let lines: usize = content.matches('\n').count() + 1;
+        if line < first_line + lines {
+            return (name.as_str(), line - first_line + 1);
+        }
+        first_line += lines;
+    }
+    ("input", line)
+}
+
 /// Runnable used to call the core libraries
 fn run_main() -> Result<(), Box<dyn std::error::Error>> {
     // Bind args from clap
-    let args: Args = Args::parse();
+    let args: Args = Args::parse_with_config()?;
 
-    let data: String = args.get_data()?;
+    let sources: Vec<(String, String)> = args.get_sources()?;
+    let data: String = sources
+        .iter()
+        .map(|(_, content)| content.as_str())
+        .collect::<Vec<&str>>()
+        .join("\n");
 
-    if contains_sql_tables(data.as_str()) {
-        let output_content: String = process_data(
-            data.as_str(),
-            args.get_restrictions().as_ref(),
-            args.get_legend(),
-            args.get_dark_mode(),
-        );
-        let file_ext: &str = args.get_output_file_ext();
+    let schema: Schema = match args.get_dialect() {
+        Some(dialect) => {
+            parse_schema_with_dialect(data.as_str(), args.get_restrictions().as_ref(), dialect)
+        }
+        None => parse_schema(data.as_str(), args.get_restrictions().as_ref()),
+    };
+    // The input is only searched for tables once the schema is found empty, as they may all
+    // have been excluded by the restrictions
+    if schema.get_tables().is_empty() && !contains_sql_tables(data.as_str()) {
+        return Err(DoteurCliError::no_table_found().into());
+    }
+    for diagnostic in schema.get_diagnostics() {
+        let (source, line): (&str, usize) = locate_line(&sources, diagnostic.get_line());
+        eprintln!("warning: {}\n", diagnostic.display_at(source, line));
+    }
+    if args.get_strict() && !schema.get_diagnostics().is_empty() {
+        return Err(DoteurCliError::not_understood(schema.get_diagnostics().len()).into());
+    }
+    if let Some(focus) = args.get_focus() {
+        if schema.get_table(focus).is_none() {
+            return Err(DoteurCliError::bad_input(&format!(
+                "The focused table {} hasn't been found in the input",
+                focus
+            ))
+            .into());
+        }
+    }
+    // The dot output is shared by the outputs rendered with graphviz
+    let dot_content: OnceCell<String> = OnceCell::new();
+    let mut failures: usize = 0;
+    for (output, format) in args.get_outputs() {
+        // The messages are written to stderr, so that stdout only holds the outputs
+        let destination: String = match output.as_str() {
+            STANDARD_STREAM => String::from("standard output"),
+            _ => format!("{} file", output),
+        };
+        match write_output(&schema, &args, &output, &format, &dot_content) {
+            Ok(_) => eprintln!(
+                "The output has been successfully written to the {}",
+                destination
+            ),
+            Err(err) => {
+                eprintln!("The {} couldn't be written : {}", destination, err);
+                failures += 1;
+            }
+        }
+    }
+    if failures > 0 {
+        return Err(DoteurCliError::outputs_failed(failures).into());
+    }
+    Ok(())
+}
 
+/// Renders the schema in the given format and writes it
+///
+/// # Arguments
+///
+/// * `schema` - The parsed schema
+/// * `args` - The CLI args
+/// * `output` - The name of the output file, or - for the standard output
+/// * `file_ext` - The format to render
+/// * `dot_content` - The dot output, rendered once for every output needing it
+fn write_output(
+    schema: &Schema,
+    args: &Args,
+    output: &str,
+    file_ext: &str,
+    dot_content: &OnceCell<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let get_dot_content = || {
+        dot_content.get_or_init(|| {
+            render_dot(
+                schema,
+                args.get_legend(),
+                args.get_dark_mode(),
+                args.get_crows_foot(),
+                args.get_verbosity(),
+            )
+        })
+    };
+    match file_ext {
+        "dot" => {
+            write_content(get_dot_content(), output)?;
+        }
+        // The other outputs don't require graphviz
+        "mmd" | "mermaid" => {
+            let output_content: String = render_mermaid(schema);
+            write_content(output_content.as_str(), output)?;
+        }
+        "puml" => {
+            let output_content: String = render_plantuml(schema);
+            write_content(output_content.as_str(), output)?;
+        }
+        "dbml" => {
+            let output_content: String = render_dbml(schema);
+            write_content(output_content.as_str(), output)?;
+        }
+        "json" => {
+            let output_content: String = render_json(schema);
+            write_content(output_content.as_str(), output)?;
+        }
+        // Without graphviz, the svg output can still be rendered natively
+        #[cfg(feature = "native_svg")]
+        "svg" if args.get_native() || which("dot").is_err() => {
+            let output_content: String = render_svg(
+                schema,
+                args.get_legend(),
+                args.get_dark_mode(),
+                args.get_crows_foot(),
+                args.get_verbosity(),
+            );
+            write_content(output_content.as_str(), output)?;
+        }
         // If it required to render in another format than the dot one, we need to check if
         // the graphviz library is in the system's path
-        if file_ext != "dot" {
+        _ => {
             if which("dot").is_err() {
-                Err(DoteurCliError::dot_exe_not_in_path().into())
-            } else if !args.can_render_with_graphviz() {
-                Err(DoteurCliError::ext_not_supported(&POSSIBLE_DOTS_OUTPUT.join(";")).into())
-            } else {
-                let temp_dir = env::temp_dir();
-                let temp_file_location = format!("{}/.output.dot", temp_dir.to_str().unwrap());
-                write_output_to_file(output_content.as_str(), &temp_file_location)?;
-                Command::new("dot")
-                    .arg(["-T", file_ext].join(""))
-                    .arg(&temp_file_location)
-                    .arg(["-o", args.get_output_filename()].join(""))
-                    .spawn()?;
-
-                println!(
-                    "The output has been successfully written to the {} file",
-                    args.get_output_filename()
+                return Err(DoteurCliError::dot_exe_not_in_path().into());
+            } else if !can_render_with_graphviz(file_ext) {
+                return Err(
+                    DoteurCliError::ext_not_supported(&POSSIBLE_DOTS_OUTPUT.join(";")).into(),
                 );
-                Ok(())
             }
-        } else {
-            write_output_to_file(output_content.as_str(), args.get_output_filename())?;
-            println!(
-                "The output has been successfully written to the {} file",
-                args.get_output_filename()
-            );
-            Ok(())
+            render_with_graphviz(get_dot_content(), file_ext, output)?;
         }
-    } else {
-        Err(DoteurCliError::no_table_found().into())
     }
+    Ok(())
+}
+
+/// Writes the content to the output file, or to the standard output
+///
+/// # Arguments
+///
+/// * `content` - The content to write
+/// * `output` - The name of the output file, or - for the standard output
+fn write_content(content: &str, output: &str) -> Result<(), Box<dyn std::error::Error>> {
+    match output {
+        STANDARD_STREAM => {
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(content.as_bytes())?;
+            stdout.flush()?;
+        }
+        _ => write_output_to_file(content, output)?,
+    }
+    Ok(())
+}
+
+/// Renders the dot content with graphviz in the given format, waiting for it to finish
+///
+/// The dot content is piped to graphviz, whose error output is returned if it fails, and
+/// shown as warnings otherwise.
+///
+/// # Arguments
+///
+/// * `dot_content` - The dot output to render
+/// * `file_ext` - The format to render
+/// * `output` - The name of the output file, or - for the standard output
+fn render_with_graphviz(
+    dot_content: &str,
+    file_ext: &str,
+    output: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut command: Command = Command::new("dot");
+    command.arg(["-T", file_ext].join(""));
+    // Graphviz writes to the standard output when no output file is given
+    match output {
+        STANDARD_STREAM => command.stdout(Stdio::inherit()),
+        _ => command.arg(["-o", output].join("")).stdout(Stdio::null()),
+    };
+    let mut child: Child = command
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // The content is written from another thread, so that graphviz can't be blocked while
+    // writing its error output
+    let mut stdin: ChildStdin = child
+        .stdin
+        .take()
+        .ok_or("The input of graphviz isn't piped")?;
+    let content: String = dot_content.to_string();
+    let writer = thread::spawn(move || stdin.write_all(content.as_bytes()));
+    let result: Output = child.wait_with_output()?;
+    let written: std::io::Result<()> = writer
+        .join()
+        .map_err(|_| "The input couldn't be piped to graphviz")?;
+    let stderr: String = String::from_utf8_lossy(&result.stderr).trim().to_string();
+    if !result.status.success() {
+        return Err(DoteurCliError::graphviz_failed(&match stderr.is_empty() {
+            true => result.status.to_string(),
+            false => stderr,
+        })
+        .into());
+    }
+    written?;
+    if !stderr.is_empty() {
+        eprintln!("warning: {}", stderr);
+    }
+    Ok(())
 }
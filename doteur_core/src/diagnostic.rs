@@ -0,0 +1,141 @@
+// Copyright ⓒ 2021-2024 LABEYE Loïc
+// This tool is distributed under the MIT License, check out [here](https://github.com/nag763/doteur/blob/main/LICENCE.MD).
+
+use std::fmt;
+
+/// A diagnostic describes a part of the input that couldn't be understood
+///
+/// It points at the line and column of the input where the issue has been found, along with
+/// the line itself so that it can be shown to the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The description of the issue
+    message: String,
+    /// The line of the input where the issue is, starting from 1
+    line: usize,
+    /// The column of the input where the issue is, starting from 1
+    column: usize,
+    /// The line of the input where the issue is
+    snippet: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_at("input", self.line))
+    }
+}
+
+impl Diagnostic {
+    /// Creates a new diagnostic
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The whole input
+    /// * `offset` - The position of the issue in the input, in bytes
+    /// * `message` - The description of the issue
+    pub(crate) fn new(data: &str, offset: usize, message: &str) -> Diagnostic {
+        let offset: usize = offset.min(data.len());
+        let line_start: usize = data[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end: usize = data[offset..].find('\n').map_or(data.len(), |i| offset + i);
+        Diagnostic {
+            message: message.to_string(),
+            line: data[..offset].matches('\n').count() + 1,
+            column: data[line_start..offset].chars().count() + 1,
+            snippet: data[line_start..line_end].trim_end().to_string(),
+        }
+    }
+
+    /// Returns the description of the issue
+    pub fn get_message(&self) -> &str {
+        self.message.as_str()
+    }
+
+    /// Returns the line of the input where the issue is, starting from 1
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the column of the input where the issue is, starting from 1
+    pub fn get_column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the line of the input where the issue is
+    pub fn get_snippet(&self) -> &str {
+        self.snippet.as_str()
+    }
+
+    /// Format the diagnostic as if it was found in the given source, at the given line
+    ///
+    /// This is useful when the parsed input is made of several sources, such as several files,
+    /// as the lines of the diagnostics are the ones of the whole input.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The name of the source, such as a file name
+    /// * `line` - The line of the issue within the source
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doteur_core::parse_schema;
+    /// let schema = parse_schema("CREATE TABLE foo (id INT);\nCREATE TABLE bar AS SELECT 1;", None);
+    /// let diagnostic = &schema.get_diagnostics()[0];
+    /// assert_eq!(diagnostic.get_line(), 2);
+    /// assert!(diagnostic.display_at("schema.sql", 2).contains("--> schema.sql:2:1"));
+    /// ```
+    pub fn display_at(&self, source: &str, line: usize) -> String {
+        let gutter: String = " ".repeat(line.to_string().len());
+        let padding: String = self
+            .snippet
+            .chars()
+            .take(self.column - 1)
+            .map(|c| match c {
+                '\t' => '\t',
+                _ => ' ',
+            })
+            .collect();
+        format!(
+            "{message}\n{gutter}--> {source}:{line}:{column}\n{gutter} |\n{line} | {snippet}\n{gutter} | {padding}^",
+            message = self.message,
+            gutter = gutter,
+            source = source,
+            line = line,
+            column = self.column,
+            snippet = self.snippet,
+            padding = padding
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_diagnostic() {
+        let data: &str = "CREATE TABLE foo (\n\tid INT,\n\tbar ???\n);";
+        let diagnostic: Diagnostic = Diagnostic::new(data, data.find("bar").unwrap(), "oops");
+        assert_eq!(diagnostic.get_line(), 3, "line");
+        assert_eq!(diagnostic.get_column(), 2, "column");
+        assert_eq!(diagnostic.get_snippet(), "\tbar ???", "snippet");
+        assert_eq!(
+            diagnostic.to_string(),
+            "oops\n --> input:3:2\n  |\n3 | \tbar ???\n  | \t^",
+            "display"
+        );
+        assert_eq!(
+            diagnostic.display_at("foo.sql", 12),
+            "oops\n  --> foo.sql:12:2\n   |\n12 | \tbar ???\n   | \t^",
+            "display at"
+        );
+
+        let diagnostic: Diagnostic = Diagnostic::new(data, 0, "oops");
+        assert_eq!(
+            (diagnostic.get_line(), diagnostic.get_column()),
+            (1, 1),
+            "start of the input"
+        );
+    }
+}
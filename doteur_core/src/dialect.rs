@@ -0,0 +1,158 @@
+// Copyright ⓒ 2021-2024 LABEYE Loïc
+// This tool is distributed under the MIT License, check out [here](https://github.com/nag763/doteur/blob/main/LICENCE.MD).
+
+use crate::lexer::{tokenize, TokenKind};
+use regex::Regex;
+
+lazy_static! {
+    ///Header of the comments written by pg_dump
+    static ref RE_POSTGRES_HEADER : Regex = Regex::new(r####"(?i)PostgreSQL\s+database\s+dump"####).unwrap();
+    ///Markers only found in PostgreSQL inputs, the types and functions shared with MySQL such
+    ///as SERIAL or nextval not being markers
+    static ref RE_POSTGRES_MARKERS : Regex = Regex::new(r####"(?i)(pg_catalog\.|ALTER\s+TABLE\s+ONLY\b|\bOWNER\s+TO\b|::\w+|CREATE\s+(?:SEQUENCE|EXTENSION)\b|\bAS\s+ENUM\b|\bGENERATED\s+(?:ALWAYS|BY\s+DEFAULT)\s+AS\s+IDENTITY\b)"####).unwrap();
+    ///Markers only found in SQLite inputs
+    static ref RE_SQLITE_MARKERS : Regex = Regex::new(r####"(?i)(\bAUTOINCREMENT\b|\bWITHOUT\s+ROWID\b|\bPRAGMA\b|\bsqlite_sequence\b)"####).unwrap();
+}
+
+/// The SQL dialect of an input
+///
+/// The dialect changes the way some parts of the input are understood, such as the comment
+/// syntax.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    /// MySQL and MariaDB dialect
+    MySql,
+    /// SQLite dialect
+    Sqlite,
+    /// PostgreSQL dialect
+    PostgreSql,
+}
+
+impl Dialect {
+    /// Guess the dialect of the given input
+    ///
+    /// If no marker of PostgreSQL or SQLite is found, the input is considered as MySQL. The
+    /// markers are only searched outside of the string literals and the comments, except for
+    /// the header of pg_dump. If the guess is wrong, the dialect can be given with
+    /// [`crate::parse_schema_with_dialect`].
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The SQL content as a string
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doteur_core::dialect::Dialect;
+    /// assert_eq!(Dialect::detect("CREATE TABLE `foo` (id INT) ENGINE=InnoDB;"), Dialect::MySql);
+    /// assert_eq!(Dialect::detect("ALTER TABLE ONLY public.foo OWNER TO bar;"), Dialect::PostgreSql);
+    /// assert_eq!(Dialect::detect("CREATE TABLE foo (id INTEGER PRIMARY KEY AUTOINCREMENT);"), Dialect::Sqlite);
+    /// ```
+    pub fn detect(data: &str) -> Dialect {
+        // The input is tokenized following the rules of MySQL, the default dialect
+        let mut code: String = String::with_capacity(data.len());
+        let mut comments: String = String::new();
+        for token in tokenize(data, Dialect::MySql) {
+            match token.get_kind() {
+                TokenKind::Comment => {
+                    comments.push_str(token.get_text());
+                    comments.push('\n');
+                }
+                TokenKind::String => code.push_str("''"),
+                _ => code.push_str(token.get_text()),
+            }
+        }
+        if RE_POSTGRES_HEADER.is_match(&comments) || RE_POSTGRES_MARKERS.is_match(&code) {
+            Dialect::PostgreSql
+        } else if RE_SQLITE_MARKERS.is_match(&code) {
+            Dialect::Sqlite
+        } else {
+            Dialect::MySql
+        }
+    }
+
+    /// Returns the dialect from a str
+    ///
+    /// None is returned for unknown dialects
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The name of the dialect
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doteur_core::dialect::Dialect;
+    /// assert_eq!(Dialect::from_str("mysql"), Some(Dialect::MySql));
+    /// assert_eq!(Dialect::from_str("PostgreSQL"), Some(Dialect::PostgreSql));
+    /// assert_eq!(Dialect::from_str("foo"), None);
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(dialect: &str) -> Option<Dialect> {
+        match dialect.trim().to_lowercase().as_str() {
+            "mysql" | "mariadb" => Some(Dialect::MySql),
+            "sqlite" | "sqlite3" => Some(Dialect::Sqlite),
+            "postgresql" | "postgres" => Some(Dialect::PostgreSql),
+            _ => None,
+        }
+    }
+
+    /// Whether a `#` starts a comment until the end of the line
+    pub fn has_hash_comments(&self) -> bool {
+        matches!(self, Dialect::MySql)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            Dialect::detect("CREATE TABLE foo (id INT);"),
+            Dialect::MySql,
+            "default"
+        );
+        assert_eq!(
+            Dialect::detect("--\n-- PostgreSQL database dump\n--\n"),
+            Dialect::PostgreSql,
+            "pg_dump header"
+        );
+        assert_eq!(
+            Dialect::detect(
+                "ALTER TABLE ONLY public.foo ADD CONSTRAINT foo_pkey PRIMARY KEY (id);"
+            ),
+            Dialect::PostgreSql,
+            "alter table only"
+        );
+        assert_eq!(
+            Dialect::detect("CREATE TABLE foo (bar varchar DEFAULT 'x'::character varying);"),
+            Dialect::PostgreSql,
+            "cast"
+        );
+        assert_eq!(
+            Dialect::detect("CREATE TABLE foo (id INTEGER PRIMARY KEY) WITHOUT ROWID;"),
+            Dialect::Sqlite,
+            "without rowid"
+        );
+        assert_eq!(
+            Dialect::detect("CREATE TABLE foo (id SERIAL, b BIGINT DEFAULT nextval(s));"),
+            Dialect::MySql,
+            "types and functions shared with mysql"
+        );
+        assert_eq!(
+            Dialect::detect(
+                "CREATE TABLE foo (id INT COMMENT 'See Foo::Bar') ENGINE=InnoDB; -- Foo::Bar"
+            ),
+            Dialect::MySql,
+            "markers in strings and comments"
+        );
+        assert_eq!(
+            Dialect::detect("-- PRAGMA foreign_keys=ON;\nCREATE TABLE foo (bar TEXT);"),
+            Dialect::MySql,
+            "sqlite marker in a comment"
+        );
+    }
+}
@@ -3,9 +3,10 @@
 
 use std::fmt;
 
-use crate::DoteurCoreError;
+use crate::schema::{Column, Verbosity};
 
 use super::super::add_traits::Trim;
+use super::escape_label;
 
 /// The attribute type
 enum AttributeType {
@@ -32,6 +33,9 @@ pub struct Attribute {
     foreign_table: Option<String>,
     /// The refered key if appliable
     foreign_key: Option<String>,
+    /// The attributes of the cells showing the comment of the column on hover, empty if
+    /// the column has no comment
+    tooltip: String,
     /// Whether the output needs to be rendered
     /// for dm or not
     dark_mode: bool,
@@ -48,9 +52,9 @@ impl fmt::Display for Attribute {
                 write!(
                     f,
                     "
-        <TR><TD ALIGN=\"LEFT\" BORDER=\"0\">
+        <TR><TD ALIGN=\"LEFT\" BORDER=\"0\"{tooltip}>
         <FONT COLOR=\"{0}\" FACE=\"Roboto\"><B>{1}</B></FONT>
-        </TD><TD ALIGN=\"LEFT\">
+        </TD><TD ALIGN=\"LEFT\"{tooltip}>
         <FONT COLOR=\"{0}\" FACE=\"Roboto\">{2}</FONT>
         </TD></TR>",
                     font_color,
@@ -58,7 +62,8 @@ impl fmt::Display for Attribute {
                     self.associed_definition
                         .as_ref()
                         .unwrap()
-                        .trim_leading_trailing()
+                        .trim_leading_trailing(),
+                    tooltip = self.tooltip
                 )
             }
 
@@ -70,16 +75,17 @@ impl fmt::Display for Attribute {
                 write!(
                     f,
                     "
-        <TR><TD ALIGN=\"LEFT\" BORDER=\"0\">
+        <TR><TD ALIGN=\"LEFT\" BORDER=\"0\"{tooltip}>
         <FONT COLOR=\"{0}\" FACE=\"Roboto\"><B>{1} {2}</B></FONT>
-        </TD><TD ALIGN=\"LEFT\">
+        </TD><TD ALIGN=\"LEFT\"{tooltip}>
         <FONT FACE=\"Roboto\" COLOR=\"{0}\">Refers to <I>{3}[{4}]</I></FONT>
         </TD></TR>",
                     font_color,
                     self.name.trim_leading_trailing(),
                     FK_EMOJI,
                     self.foreign_table.as_ref().unwrap().trim_leading_trailing(),
-                    self.foreign_key.as_ref().unwrap().trim_leading_trailing()
+                    self.foreign_key.as_ref().unwrap().trim_leading_trailing(),
+                    tooltip = self.tooltip
                 )
             }
 
@@ -91,9 +97,9 @@ impl fmt::Display for Attribute {
                 write!(
                     f,
                     "
-        <TR><TD ALIGN=\"LEFT\" BORDER=\"0\">
+        <TR><TD ALIGN=\"LEFT\" BORDER=\"0\"{tooltip}>
         <FONT COLOR=\"{0}\" FACE=\"Roboto\"><B>{1} {2}</B></FONT>
-        </TD><TD ALIGN=\"LEFT\">
+        </TD><TD ALIGN=\"LEFT\"{tooltip}>
         <FONT FACE=\"Roboto\" COLOR=\"{0}\">{3}</FONT>
         </TD></TR>",
                     font_color,
@@ -102,7 +108,8 @@ impl fmt::Display for Attribute {
                     self.associed_definition
                         .as_ref()
                         .unwrap()
-                        .trim_leading_trailing()
+                        .trim_leading_trailing(),
+                    tooltip = self.tooltip
                 )
             }
 
@@ -114,9 +121,9 @@ impl fmt::Display for Attribute {
                 write!(
                     f,
                     "
-        <TR><TD ALIGN=\"LEFT\" BORDER=\"0\">
+        <TR><TD ALIGN=\"LEFT\" BORDER=\"0\"{tooltip}>
         <FONT COLOR=\"{0}\" FACE=\"Roboto\"><B>{1} {2}{3}</B></FONT>
-        </TD><TD ALIGN=\"LEFT\">
+        </TD><TD ALIGN=\"LEFT\"{tooltip}>
         <FONT FACE=\"Roboto\" COLOR=\"{0}\">Refers to <I>{4}[{5}]</I></FONT>
         </TD></TR>",
                     font_color,
@@ -124,7 +131,8 @@ impl fmt::Display for Attribute {
                     PK_EMOJI,
                     FK_EMOJI,
                     self.foreign_table.as_ref().unwrap().trim_leading_trailing(),
-                    self.foreign_key.as_ref().unwrap().trim_leading_trailing()
+                    self.foreign_key.as_ref().unwrap().trim_leading_trailing(),
+                    tooltip = self.tooltip
                 )
             }
         }
@@ -132,161 +140,30 @@ impl fmt::Display for Attribute {
 }
 
 impl Attribute {
-    /// Define a new sql column
-    pub fn new_col_def(name: String, associed_definition: String, dark_mode: bool) -> Attribute {
+    /// Define a new attribute from a column, its definition being described with the given
+    /// verbosity
+    pub fn new(column: &Column, dark_mode: bool, verbosity: Verbosity) -> Attribute {
+        let attribute_type: AttributeType =
+            match (column.is_primary_key(), column.get_foreign_key()) {
+                (true, Some(_)) => AttributeType::PkFk,
+                (true, None) => AttributeType::Pk,
+                (false, Some(_)) => AttributeType::Fk,
+                (false, None) => AttributeType::ColDef,
+            };
         Attribute {
-            name,
-            attribute_type: AttributeType::ColDef,
-            associed_definition: Some(associed_definition),
-            foreign_table: None,
-            foreign_key: None,
-            dark_mode,
-        }
-    }
-
-    /// Define a new primary key
-    pub fn new_pk(name: String, associed_definition: String, dark_mode: bool) -> Attribute {
-        Attribute {
-            name,
-            attribute_type: AttributeType::Pk,
-            associed_definition: Some(associed_definition),
-            foreign_table: None,
-            foreign_key: None,
-            dark_mode,
-        }
-    }
-
-    /// Add PK nature to the current attribute
-    pub fn add_pk_nature(&mut self) {
-        match self.attribute_type {
-            AttributeType::Fk => {
-                self.attribute_type = AttributeType::PkFk;
-            }
-            AttributeType::ColDef => self.attribute_type = AttributeType::Pk,
-            _ => (),
-        };
-    }
-
-    /// Add FK nature to a current attribute
-    pub fn add_fk_nature(&mut self, foreign_table: String, foreign_key: String) {
-        match self.attribute_type {
-            AttributeType::Pk => {
-                self.attribute_type = AttributeType::PkFk;
-                self.foreign_table = Some(foreign_table);
-                self.foreign_key = Some(foreign_key);
-            }
-            AttributeType::ColDef => {
-                self.attribute_type = AttributeType::Fk;
-                self.foreign_table = Some(foreign_table);
-                self.foreign_key = Some(foreign_key);
-            }
-            _ => (),
-        };
-    }
-}
-
-/// Trait for retrieving and modifying values from a vec
-pub(crate) trait KeyValueMap {
-    /// Returns the index of an attribute
-    ///
-    /// # Arguments
-    ///
-    /// * `attr_name` - Name of the attribute to be retrieved
-    fn index_of_attribute(&mut self, attr_name: &str) -> Result<usize, DoteurCoreError>;
-
-    /// Add PK nature to an attribute in the vec
-    ///
-    /// # Arguments
-    ///
-    /// * `attr_name` - Name of the attribute to be retrieved
-    fn add_pk_nature_to_attribute(&mut self, attr_name: &str) -> Result<usize, DoteurCoreError>;
-
-    /// Add FK nature to an existing attribute
-    ///
-    /// # Arguments
-    ///
-    /// * `attr_name` - Name of the attribute to be retrieved
-    /// * `foreign_table` - Name of the refered table
-    /// * `foreign_key` - Name of the refered key
-    fn add_fk_nature_to_attribute(
-        &mut self,
-        attr_name: &str,
-        foreign_table: &str,
-        foreign_key: &str,
-    ) -> Result<usize, DoteurCoreError>;
-
-    /// Push attribute or replace it
-    ///
-    /// # `attr_name` - Name of the attribute to be retrieved
-    fn push_or_replace_attribute(&mut self, value: Attribute);
-}
-
-impl KeyValueMap for Vec<Attribute> {
-    fn index_of_attribute(&mut self, attr_name: &str) -> Result<usize, DoteurCoreError> {
-        let index: Option<usize> = self
-            .iter_mut()
-            .enumerate()
-            .filter(|(_, attr)| attr.name == attr_name)
-            .map(|(i, _)| i)
-            .last();
-
-        match index {
-            Some(v) => Ok(v),
-            None => Err(DoteurCoreError::logic_error(
-                format!("Attribute {} not found", attr_name).as_str(),
-                file!(),
-                line!(),
-            )),
-        }
-    }
-
-    fn push_or_replace_attribute(&mut self, value: Attribute) {
-        match self.index_of_attribute(value.name.as_str()) {
-            Ok(index) => {
-                let _ = std::mem::replace(&mut self[index], value);
-            }
-            Err(_) => self.push(value),
-        };
-    }
-
-    fn add_pk_nature_to_attribute(&mut self, attr_name: &str) -> Result<usize, DoteurCoreError> {
-        match self.index_of_attribute(attr_name) {
-            Ok(index) => {
-                self[index].add_pk_nature();
-                Ok(index)
-            }
-            Err(_) => Err(DoteurCoreError::logic_error(
-                format!(
-                    "Can't add pk nature to the {} attribute not present in the vec",
-                    attr_name
-                )
-                .as_str(),
-                file!(),
-                line!(),
-            )),
-        }
-    }
-
-    fn add_fk_nature_to_attribute(
-        &mut self,
-        attr_name: &str,
-        foreign_table: &str,
-        foreign_key: &str,
-    ) -> Result<usize, DoteurCoreError> {
-        match self.index_of_attribute(attr_name) {
-            Ok(index) => {
-                self[index].add_fk_nature(foreign_table.to_string(), foreign_key.to_string());
-                Ok(index)
-            }
-            Err(_) => Err(DoteurCoreError::logic_error(
+            name: column.get_name().to_string(),
+            attribute_type,
+            associed_definition: Some(escape_label(&column.get_description(verbosity))),
+            foreign_table: column.get_foreign_key().map(|r| r.get_table().to_string()),
+            foreign_key: column.get_foreign_key().map(|r| r.get_column().to_string()),
+            // Graphviz only uses the tooltip of a cell if it has a link
+            tooltip: column.get_comment().map_or(String::new(), |comment| {
                 format!(
-                    "Can't add fk nature to the {} attribute not present in the vec",
-                    attr_name
+                    " HREF=\"#\" TOOLTIP=\"{}\"",
+                    escape_label(comment).replace('"', "&quot;")
                 )
-                .as_str(),
-                file!(),
-                line!(),
-            )),
+            }),
+            dark_mode,
         }
     }
 }
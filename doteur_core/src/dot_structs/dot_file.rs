@@ -3,69 +3,156 @@
 
 use std::fmt;
 
+use crate::schema::{Schema, Table, Verbosity};
+
 use super::dot_table::DotTable;
-use super::relation::Relation;
+use super::relation::{generate_dot_output, generate_view_dependency};
 
 /// A DotFile object is used to render the compiled schema in argument.
-pub struct DotFile {
+pub struct DotFile<'a> {
     /// The header of the dot file
     header: String,
-    /// The tables to include in the file
+    /// The tables to include in the file outside of any cluster, the views and the stubs
     dot_tables: Vec<DotTable>,
-    /// The relations to include in the file
-    relations: Vec<Relation>,
+    /// The tables grouped by schema, when they span several schemas
+    clusters: Vec<(String, Vec<DotTable>)>,
+    /// The schema whose relations are included in the file
+    schema: &'a Schema,
     /// The footer of the file
     footer: String,
     /// Define if the graph has to be in dark mode
     dark_mode: bool,
+    /// Define if the relations are drawn with the crow's foot notation
+    crows_foot: bool,
 }
 
-impl fmt::Display for DotFile {
+impl fmt::Display for DotFile<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{0}\n{1}\n{2}\n\n{3}",
+            "{0}\n{1}\n{2}\n{3}\n{4}",
             self.header,
-            self.dot_tables
+            self.clusters
                 .iter()
-                .map(|s| s.to_string())
+                .map(|(name, dot_tables)| generate_cluster(name, dot_tables, self.dark_mode))
+                .chain(self.dot_tables.iter().map(|s| s.to_string()))
                 .collect::<Vec<String>>()
                 .join("\n"),
-            self.relations
+            self.schema
+                .get_relations()
                 .iter()
-                .map(|r| r.clone().generate_dot_output(self.dark_mode))
+                .map(|r| generate_dot_output(r, self.dark_mode, self.crows_foot))
                 .collect::<Vec<String>>()
                 .join("\n"),
+            // The dependencies of the views are only drawn toward the rendered tables and views
+            self.schema
+                .get_views()
+                .iter()
+                .flat_map(|view| view
+                    .get_dependencies()
+                    .iter()
+                    .filter(|dependency| self.schema.get_table(dependency).is_some()
+                        || self.schema.get_view(dependency).is_some())
+                    .map(move |dependency| generate_view_dependency(
+                        view.get_name(),
+                        dependency,
+                        self.dark_mode
+                    )))
+                .map(|dependency| format!("{}\n", dependency))
+                .collect::<String>(),
             self.footer
         )
     }
 }
 
-impl DotFile {
-    /// Creates a new dotfile with the given name
-    pub fn new(legend: bool, dark_mode: bool) -> DotFile {
+impl<'a> DotFile<'a> {
+    /// Creates a new dotfile from the given schema
+    ///
+    /// When the tables span several schemas, the tables of each schema are grouped in a
+    /// cluster.
+    pub fn new(
+        schema: &'a Schema,
+        legend: bool,
+        dark_mode: bool,
+        crows_foot: bool,
+        verbosity: Verbosity,
+    ) -> DotFile<'a> {
+        let schema_names: Vec<&str> = match schema.get_schema_names() {
+            names if names.len() > 1 => names,
+            _ => Vec::new(),
+        };
+        let is_clustered = |table: &&Table| {
+            table
+                .get_schema()
+                .is_some_and(|name| schema_names.contains(&name))
+        };
         DotFile {
-            header: init_dot(legend, dark_mode),
-            dot_tables: Vec::new(),
-            relations: Vec::new(),
+            header: init_dot(legend, dark_mode, crows_foot),
+            clusters: schema_names
+                .iter()
+                .map(|name| {
+                    (
+                        name.to_string(),
+                        schema
+                            .get_tables()
+                            .iter()
+                            .filter(|table| table.get_schema() == Some(name))
+                            .map(|table| DotTable::new(table, dark_mode, verbosity))
+                            .collect(),
+                    )
+                })
+                .collect(),
+            dot_tables: schema
+                .get_tables()
+                .iter()
+                .filter(|table| !is_clustered(table))
+                .map(|table| DotTable::new(table, dark_mode, verbosity))
+                .chain(
+                    schema
+                        .get_views()
+                        .iter()
+                        .map(|view| DotTable::new_view(view, dark_mode)),
+                )
+                .chain(
+                    schema
+                        .get_stubs()
+                        .iter()
+                        .map(|stub| DotTable::new_stub(stub, dark_mode)),
+                )
+                .collect(),
+            schema,
             footer: String::from("}"),
             dark_mode,
+            crows_foot,
         }
     }
+}
 
-    /// Adds a table to the DotFile
-    pub fn add_table(&mut self, table: DotTable) {
-        self.dot_tables.push(table);
-    }
-
-    /// Add a relation to the DotFile
-    pub fn add_relation(&mut self, relation: Relation) {
-        self.relations.push(relation);
-    }
+/// Generate the cluster grouping the tables of a schema, labeled with its name
+fn generate_cluster(name: &str, dot_tables: &[DotTable], dark_mode: bool) -> String {
+    format!(
+        "
+    subgraph \"cluster_{0}\" {{
+        label = \"{0}\";
+        style = \"rounded\";
+        {1}
+{2}
+    }}",
+        name,
+        match dark_mode {
+            true => "color = \"white\"; fontcolor = \"white\";",
+            false => "color = \"grey50\";",
+        },
+        dot_tables
+            .iter()
+            .map(|dot_table| dot_table.to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    )
 }
 
 /// Creates the dot file header
-fn init_dot(legend: bool, dark_mode: bool) -> String {
+fn init_dot(legend: bool, dark_mode: bool, crows_foot: bool) -> String {
     let bg_color: &str = match dark_mode {
         true => "bgcolor= black;",
         false => "",
@@ -76,9 +163,9 @@ fn init_dot(legend: bool, dark_mode: bool) -> String {
         false => "black",
     };
 
-    let dot_legend: String = match legend {
-        false => String::new(),
-        true => format!(
+    let dot_legend: String = match (legend, crows_foot) {
+        (false, _) => String::new(),
+        (true, false) => format!(
             "
     {{
         labelloc=\"b\"
@@ -91,10 +178,44 @@ fn init_dot(legend: bool, dark_mode: bool) -> String {
         p1 [style = invis];
         s0 [style = invis];
         s1 [style = invis];
+        u0 [style = invis];
+        u1 [style = invis];
+        v0 [style = invis];
+        v1 [style = invis];
     }}
     d0 -> d1 [label=composition arrowhead=dot color={0} fontcolor={0}]
     p0 -> p1 [label=aggregation arrowhead=odot color={0} fontcolor={0}]
-    s0 -> s1 [label=association color={0} fontcolor={0}]",
+    s0 -> s1 [label=association color={0} fontcolor={0}]
+    u0 -> u1 [label=\"on update cascade\" style=bold color={0} fontcolor={0}]
+    v0 -> v1 [label=\"view dependency\" arrowhead=empty style=dashed color={0} fontcolor={0}]",
+            edge_color_scheme
+        ),
+        (true, true) => format!(
+            "
+    {{
+        labelloc=\"b\"
+        labeljust=\"r\"
+        rank=sink
+        rankdir=LR
+        d0 [style = invis];
+        d1 [style = invis];
+        s0 [style = invis];
+        s1 [style = invis];
+        o0 [style = invis];
+        o1 [style = invis];
+        n0 [style = invis];
+        n1 [style = invis];
+        u0 [style = invis];
+        u1 [style = invis];
+        v0 [style = invis];
+        v1 [style = invis];
+    }}
+    d0 -> d1 [label=composition arrowhead=teetee arrowtail=crowodot dir=both color={0} fontcolor={0}]
+    s0 -> s1 [label=association arrowhead=teetee arrowtail=crowodot dir=both style=dashed color={0} fontcolor={0}]
+    o0 -> o1 [label=\"one to one\" arrowhead=teetee arrowtail=teeodot dir=both style=dashed color={0} fontcolor={0}]
+    n0 -> n1 [label=optional arrowhead=teeodot arrowtail=crowodot dir=both style=dashed color={0} fontcolor={0}]
+    u0 -> u1 [label=\"on update cascade\" arrowhead=teetee arrowtail=crowodot dir=both style=\"dashed,bold\" color={0} fontcolor={0}]
+    v0 -> v1 [label=\"view dependency\" arrowhead=empty style=dashed color={0} fontcolor={0}]",
             edge_color_scheme
         ),
     };
@@ -3,91 +3,185 @@
 
 use std::fmt;
 
-use crate::errors::DoteurCoreError;
+use crate::schema::{Check, Index, Table, Verbosity, View};
 
 use super::super::add_traits::Trim;
-use super::attribute::{Attribute, KeyValueMap};
+use super::attribute::Attribute;
+use super::escape_label;
 
 /// A dot table is the corresponding rendering of a sql table in a dot file
 pub struct DotTable {
     /// Header of the table
     header: String,
-    /// The attribute of the table
-    attributes: Vec<Attribute>,
+    /// The rows describing the columns of the table
+    rows: Vec<String>,
+    /// The rows describing the indexes of the table
+    indexes: Vec<String>,
     /// The footer of the table
     footer: String,
-    /// Changes the rendering of the file if true
-    dark_mode: bool,
 }
 
 impl fmt::Display for DotTable {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{0}\n{1}\n\n\t{2}\n",
+            "{0}\n{1}\n{2}\n\t{3}\n",
             self.header,
-            self.attributes
-                .iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>()
-                .join("\n"),
+            self.rows.join("\n"),
+            // The indexes are separated from the columns by a line
+            match self.indexes.is_empty() {
+                true => String::new(),
+                false => format!("\n        <HR/>{}\n", self.indexes.join("\n")),
+            },
             self.footer
         )
     }
 }
 
 impl DotTable {
-    /// Creates a new table
-    pub fn new(table_name: &str, dark_mode: bool) -> DotTable {
-        let header: String = generate_table_header(table_name, dark_mode);
+    /// Creates a new dot table from a table
+    ///
+    /// The check constraints of the table are only shown with the full verbosity.
+    pub fn new(table: &Table, dark_mode: bool, verbosity: Verbosity) -> DotTable {
+        let header: String = generate_table_header(table.get_name(), false, dark_mode);
+        let checks: &[Check] = match verbosity {
+            Verbosity::Full => table.get_checks(),
+            _ => &[],
+        };
         DotTable {
             header,
-            attributes: Vec::new(),
-            footer: String::from("</TABLE> >]"),
-            dark_mode,
+            rows: table
+                .get_columns()
+                .iter()
+                .map(|column| Attribute::new(column, dark_mode, verbosity).to_string())
+                .collect(),
+            indexes: table
+                .get_indexes()
+                .iter()
+                .map(|index| generate_index_row(index, dark_mode))
+                .chain(
+                    checks
+                        .iter()
+                        .map(|check| generate_check_row(check, dark_mode)),
+                )
+                .collect(),
+            footer: generate_table_footer(table.get_comment()),
         }
     }
 
-    /// Adds an attribute to the table
-    pub fn add_attribute(&mut self, title: &str, desc: &str) {
-        self.attributes.push(Attribute::new_col_def(
-            title.to_string(),
-            desc.to_string(),
-            self.dark_mode,
-        ));
+    /// Creates a new dot table from a view, its header being colored differently
+    pub fn new_view(view: &View, dark_mode: bool) -> DotTable {
+        let header: String = generate_table_header(view.get_name(), true, dark_mode);
+        DotTable {
+            header,
+            rows: view
+                .get_columns()
+                .iter()
+                .map(|column| generate_view_row(column, dark_mode))
+                .collect(),
+            indexes: Vec::new(),
+            footer: generate_table_footer(None),
+        }
     }
 
-    /// Adds a PK to the table
-    pub fn add_attribute_pk(&mut self, key: &str, desc: &str) {
-        self.attributes.push_or_replace_attribute(Attribute::new_pk(
-            key.to_string(),
-            desc.to_string(),
-            self.dark_mode,
-        ));
+    /// Creates a new dot table standing for a table that isn't rendered, only its name being
+    /// shown greyed out
+    pub fn new_stub(name: &str, dark_mode: bool) -> DotTable {
+        DotTable {
+            header: generate_stub_header(name, dark_mode),
+            rows: Vec::new(),
+            indexes: Vec::new(),
+            footer: generate_table_footer(None),
+        }
     }
+}
 
-    /// Adds foreign key nature to given attribute
-    pub fn add_fk_nature_to_attribute(
-        &mut self,
-        key: &str,
-        fk_table: &str,
-        fk_col: &str,
-    ) -> Result<usize, DoteurCoreError> {
-        self.attributes
-            .add_fk_nature_to_attribute(key, fk_table, fk_col)
-    }
+/// Generate the .dot row describing a column of a view, whose type isn't known
+fn generate_view_row(column: &str, dark_mode: bool) -> String {
+    format!(
+        "
+        <TR><TD ALIGN=\"LEFT\" BORDER=\"0\" COLSPAN=\"2\">
+        <FONT COLOR=\"{}\" FACE=\"Roboto\"><B>{}</B></FONT>
+        </TD></TR>",
+        match dark_mode {
+            true => "white",
+            false => "black",
+        },
+        escape_label(column)
+    )
+}
+
+/// Generate the .dot row describing an index
+fn generate_index_row(index: &Index, dark_mode: bool) -> String {
+    let font_color: &str = match dark_mode {
+        true => "white",
+        false => "black",
+    };
+    format!(
+        "
+        <TR><TD ALIGN=\"LEFT\" BORDER=\"0\">{1}</TD><TD ALIGN=\"LEFT\">
+        <FONT COLOR=\"{0}\" FACE=\"Roboto\">{2} ({3})</FONT>
+        </TD></TR>",
+        font_color,
+        // The cell of the unnamed indexes is left empty
+        index.get_name().map_or(String::new(), |name| format!(
+            "
+        <FONT COLOR=\"{}\" FACE=\"Roboto\"><I>{}</I></FONT>
+        ",
+            font_color,
+            escape_label(name)
+        )),
+        match index.is_unique() {
+            true => "UNIQUE",
+            false => "INDEX",
+        },
+        escape_label(&index.get_columns().join(", "))
+    )
+}
 
-    /// Adds primary key nature to given attribute
-    pub fn add_pk_nature_to_attribute(&mut self, key: &str) -> Result<usize, DoteurCoreError> {
-        self.attributes.add_pk_nature_to_attribute(key)
+/// Generate the .dot row describing a check constraint of a table
+fn generate_check_row(check: &Check, dark_mode: bool) -> String {
+    let font_color: &str = match dark_mode {
+        true => "white",
+        false => "black",
+    };
+    format!(
+        "
+        <TR><TD ALIGN=\"LEFT\" BORDER=\"0\">{1}</TD><TD ALIGN=\"LEFT\">
+        <FONT COLOR=\"{0}\" FACE=\"Roboto\">CHECK ({2})</FONT>
+        </TD></TR>",
+        font_color,
+        // The cell of the unnamed constraints is left empty
+        check.get_name().map_or(String::new(), |name| format!(
+            "
+        <FONT COLOR=\"{}\" FACE=\"Roboto\"><I>{}</I></FONT>
+        ",
+            font_color, name
+        )),
+        escape_label(check.get_expression())
+    )
+}
+
+/// Generate the .dot table footer, the comment of the table being shown on hover
+fn generate_table_footer(comment: Option<&str>) -> String {
+    match comment {
+        Some(comment) => format!(
+            "</TABLE> >, tooltip=\"{}\"]",
+            comment.replace('\\', "\\\\").replace('"', "\\\"")
+        ),
+        None => String::from("</TABLE> >]"),
     }
 }
 
 /// Generate the .dot table header.
-fn generate_table_header(name: &str, dark_mode: bool) -> String {
-    let styles: (&str, &str) = match dark_mode {
-        true => ("grey20", "grey10"),
-        false => ("grey95", "indigo"),
+///
+/// The header of the views is colored differently than the one of the tables.
+fn generate_table_header(name: &str, view: bool, dark_mode: bool) -> String {
+    let styles: (&str, &str) = match (dark_mode, view) {
+        (true, false) => ("grey20", "grey10"),
+        (true, true) => ("grey20", "darkslategray"),
+        (false, false) => ("grey95", "indigo"),
+        (false, true) => ("grey95", "darkcyan"),
     };
     format!(
         "
@@ -103,3 +197,24 @@ fn generate_table_header(name: &str, dark_mode: bool) -> String {
         styles.1
     )
 }
+
+/// Generate the .dot header of a stub, greyed out and with a dashed border
+fn generate_stub_header(name: &str, dark_mode: bool) -> String {
+    let styles: (&str, &str) = match dark_mode {
+        true => ("grey20", "grey60"),
+        false => ("grey95", "grey50"),
+    };
+    format!(
+        "
+    \"{0}\" [label=<
+        <TABLE BGCOLOR=\"{1}\" COLOR=\"{2}\" BORDER=\"1\" CELLBORDER=\"0\" CELLSPACING=\"0\" STYLE=\"dashed\">
+
+        <TR><TD CELLPADDING=\"5\" ALIGN=\"CENTER\">
+        <FONT FACE=\"Roboto\" COLOR=\"{2}\" POINT-SIZE=\"12\">
+        <I>{0}</I>
+        </FONT></TD></TR>",
+        name.trim_leading_trailing(),
+        styles.0,
+        styles.1
+    )
+}
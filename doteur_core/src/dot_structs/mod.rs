@@ -5,3 +5,10 @@ mod attribute;
 pub(crate) mod dot_file;
 pub(crate) mod dot_table;
 pub(crate) mod relation;
+
+/// Escapes the characters having a meaning in the HTML-like labels of the DOT output
+fn escape_label(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -1,110 +1,110 @@
 // Copyright ⓒ 2021-2024 LABEYE Loïc
 // This tool is distributed under the MIT License, check out [here](https://github.com/nag763/doteur/blob/main/LICENCE.MD).
 
-const RELATE_TO_EMOJI: &str = "➡";
+use crate::schema::{Cardinality, ReferentialAction, Relation};
 
-#[derive(Debug, Clone)]
-enum OnDelete {
-    SetNull,
-    Cascade,
-    Restrict,
-    NoAction,
-    SetDefault,
-}
+const RELATE_TO_EMOJI: &str = "➡";
 
-impl OnDelete {
-    /// Returns the on delete type from a str
-    fn from_str(relation_type: &str) -> OnDelete {
-        match relation_type {
-            "SET NULL" => OnDelete::SetNull,
-            "CASCADE" => OnDelete::Cascade,
-            "NO ACTION" => OnDelete::NoAction,
-            "SET DEFAULT" => OnDelete::SetDefault,
-            _ => OnDelete::Restrict,
-        }
-    }
-    /// Return the arrow head
-    fn get_dot_arrowhead(&self) -> &str {
-        match self {
-            OnDelete::SetNull => "odot",
-            OnDelete::Cascade => "dot",
-            _ => "normal",
-        }
+/// Returns the dot arrow head matching the on delete action
+fn get_dot_arrowhead(on_delete: Option<ReferentialAction>) -> &'static str {
+    match on_delete {
+        Some(ReferentialAction::SetNull) => "odot",
+        Some(ReferentialAction::Cascade) => "dot",
+        _ => "normal",
     }
 }
 
-#[derive(Debug, Clone)]
-/// A relation is a link between a list of pair of keys
-/// between two tables
-pub struct Relation {
-    /// Table that got the refering key
-    origin_table: String,
-    /// Table that got the refered key
-    refered_table: String,
-    /// List of keys refering each others
-    keys: Vec<(String, String)>,
-    /// Relations type
-    relation_type: OnDelete,
+/// Returns the dot arrow head and arrow tail of the relation in the crow's foot notation
+///
+/// The head, on the refered side, tells whether the refering rows can exist without a
+/// refered one, while the tail, on the refering side, tells how many rows can refer the same
+/// refered row.
+fn get_dot_crows_foot(relation: &Relation) -> (&'static str, &'static str) {
+    (
+        match relation.is_optional() {
+            true => "teeodot",
+            false => "teetee",
+        },
+        match relation.get_cardinality() {
+            Cardinality::OneToOne => "teeodot",
+            Cardinality::OneToMany => "crowodot",
+        },
+    )
 }
 
-impl Relation {
-    /// A new relation
-    pub fn new(origin_table: String, refered_table: String, relation_type: String) -> Relation {
-        Relation {
-            origin_table,
-            refered_table,
-            keys: Vec::new(),
-            relation_type: OnDelete::from_str(relation_type.as_str()),
-        }
-    }
+/// Returns the relation as a dot output
+///
+/// # Arguments
+///
+/// * `relation` - The relation to render
+/// * `dark_mode` - Whether the output needs to be rendered in dark mode or not
+/// * `crows_foot` - Whether the relation is drawn with the crow's foot notation, instead of
+///   an arrow head depending on the action executed on delete
+pub fn generate_dot_output(relation: &Relation, dark_mode: bool, crows_foot: bool) -> String {
+    let color_scheme: &str = match dark_mode {
+        true => "fontcolor=white, color=white",
+        false => "",
+    };
 
-    /// A new relation with a single pair
-    pub fn new_with_single_pair(
-        origin_table: String,
-        refered_table: String,
-        origin_key: String,
-        refered_key: String,
-        relation_type: String,
-    ) -> Relation {
-        Relation {
-            origin_table,
-            refered_table,
-            keys: vec![(origin_key, refered_key)],
-            relation_type: OnDelete::from_str(relation_type.as_str()),
+    // The cascading updates are drawn in bold, the other actions only being described in the label
+    let on_update_hint: String = match relation.get_on_update_hint() {
+        Some(hint) => format!("<BR/><FONT POINT-SIZE=\"10.0\">{}</FONT>", hint),
+        None => String::new(),
+    };
+    let arrows: String = match crows_foot {
+        true => {
+            let (arrowhead, arrowtail): (&str, &str) = get_dot_crows_foot(relation);
+            format!(
+                "arrowhead = \"{}\", arrowtail = \"{}\", dir = \"both\"",
+                arrowhead, arrowtail
+            )
         }
-    }
+        false => format!(
+            "arrowhead = \"{}\"",
+            get_dot_arrowhead(relation.get_on_delete())
+        ),
+    };
+    // With the crow's foot notation, the relations not deleted along the refered row are dashed
+    let style: &str = match (
+        relation.get_on_update(),
+        crows_foot && relation.get_on_delete() != Some(ReferentialAction::Cascade),
+    ) {
+        (Some(ReferentialAction::Cascade), true) => "style = \"dashed,bold\", ",
+        (Some(ReferentialAction::Cascade), false) => "style = \"bold\", ",
+        (_, true) => "style = \"dashed\", ",
+        (_, false) => "",
+    };
 
-    /// Returns a copy of the pairs of key
-    pub fn get_pairs_of_keys(&self) -> Vec<(String, String)> {
-        self.keys.clone()
-    }
-
-    /// Returns the number of pairs of keys
-    pub fn get_number_of_pairs_of_keys(&self) -> usize {
-        self.keys.len()
-    }
-
-    /// Returns the refered table
-    pub fn get_refered_table(&self) -> &str {
-        self.refered_table.as_str()
-    }
-
-    /// Add a new pair of key to the relation
-    pub fn push_pair_of_keys(&mut self, origin_key: String, refered_key: String) {
-        self.keys.push((origin_key, refered_key));
+    let mut ret: String = String::new();
+    for key in relation.get_pairs_of_keys() {
+        ret.push_str(
+            format!(
+                "\t\"{0}\" -> \"{1}\" [label=<<I>{2} {3} {4}</I>{5}>, {6}, fontsize=\"12.0\", {7}{8}]",
+                relation.get_origin_table(),
+                relation.get_refered_table(),
+                key.0,
+                RELATE_TO_EMOJI,
+                key.1,
+                on_update_hint,
+                arrows,
+                style,
+                color_scheme
+            )
+            .as_str(),
+        );
     }
+    ret
+}
 
-    /// Returns the relations as a dot output
-    pub fn generate_dot_output(self, dark_mode: bool) -> String {
-        let color_scheme: &str = match dark_mode {
-            true => "fontcolor=white, color=white",
+/// Returns the dependency of a view toward a table or another view as a dot output
+pub fn generate_view_dependency(view: &str, dependency: &str, dark_mode: bool) -> String {
+    format!(
+        "\t\"{}\" -> \"{}\" [style = \"dashed\", arrowhead = \"empty\"{}]",
+        view,
+        dependency,
+        match dark_mode {
+            true => ", color=white",
             false => "",
-        };
-
-        let mut ret: String = String::new();
-        for key in self.keys {
-            ret.push_str(format!("\t{0} -> {1} [label=<<I>{2} {3} {4}</I>>, arrowhead = \"{5}\", fontsize=\"12.0\", {6}]", self.origin_table, self.refered_table, key.0, RELATE_TO_EMOJI, key.1, self.relation_type.get_dot_arrowhead(), color_scheme).as_str());
         }
-        ret
-    }
+    )
 }
@@ -0,0 +1,1046 @@
+// Copyright ⓒ 2021-2024 LABEYE Loïc
+// This tool is distributed under the MIT License, check out [here](https://github.com/nag763/doteur/blob/main/LICENCE.MD).
+
+use crate::diagnostic::Diagnostic;
+use crate::dialect::Dialect;
+
+/// The keywords a statement can start with, including the commands of the mysql client
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "ALTER",
+    "ANALYZE",
+    "ATTACH",
+    "BEGIN",
+    "CALL",
+    "CHECKPOINT",
+    "CLUSTER",
+    "COMMENT",
+    "COMMIT",
+    "COPY",
+    "CREATE",
+    "DEALLOCATE",
+    "DECLARE",
+    "DELETE",
+    "DETACH",
+    "DISCARD",
+    "DO",
+    "DROP",
+    "END",
+    "EXECUTE",
+    "EXPLAIN",
+    "FLUSH",
+    "GRANT",
+    "HANDLER",
+    "INSERT",
+    "LOAD",
+    "LOCK",
+    "MERGE",
+    "NOTIFY",
+    "OPTIMIZE",
+    "PRAGMA",
+    "PREPARE",
+    "REFRESH",
+    "REINDEX",
+    "RELEASE",
+    "RENAME",
+    "REPLACE",
+    "RESET",
+    "REVOKE",
+    "ROLLBACK",
+    "SAVEPOINT",
+    "SECURITY",
+    "SELECT",
+    "SET",
+    "SHOW",
+    "SOURCE",
+    "START",
+    "TRUNCATE",
+    "UNLOCK",
+    "USE",
+    "VACUUM",
+    "VALUES",
+    "WITH",
+];
+
+/// The kind of a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    /// A sequence of whitespaces
+    Whitespace,
+    /// A single line or a block comment
+    Comment,
+    /// A keyword, an unquoted identifier or a number
+    Word,
+    /// A string literal, including the dollar quoted ones
+    String,
+    /// An identifier enclosed by double quotes, backticks or brackets
+    QuotedIdentifier,
+    /// Any other character
+    Symbol,
+}
+
+/// A token of a SQL input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Token<'a> {
+    /// The kind of the token
+    kind: TokenKind,
+    /// The text of the token
+    text: &'a str,
+    /// Position of the token in the input, in bytes
+    offset: usize,
+    /// Whether the token is closed, the string literals, quoted identifiers and block comments
+    /// left open lasting until the end of the input
+    closed: bool,
+}
+
+impl Token<'_> {
+    /// Returns the kind of the token
+    pub(crate) fn get_kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    /// Returns the text of the token
+    pub(crate) fn get_text(&self) -> &str {
+        self.text
+    }
+
+    /// Whether the token is the given symbol
+    fn is_symbol(&self, symbol: char) -> bool {
+        self.kind == TokenKind::Symbol && self.text.starts_with(symbol)
+    }
+}
+
+/// A statement of a SQL input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Statement {
+    /// The content of the statement, without its delimiter
+    ///
+    /// The comments are replaced by whitespaces so that every character keeps the position
+    /// it has in the input.
+    content: String,
+    /// Position of the statement in the input, in bytes
+    offset: usize,
+}
+
+impl Statement {
+    /// Returns the content of the statement, with its comments blanked out
+    pub(crate) fn get_content(&self) -> &str {
+        self.content.as_str()
+    }
+
+    /// Returns the position of the statement in the input, in bytes
+    pub(crate) fn get_offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// Whether the character can be part of a word
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Returns the length of a quoted token starting with the given quote
+///
+/// A doubled quote stands for the quote itself, the backslashes escape the next character if
+/// they are enabled. None is returned if the token isn't closed.
+fn quoted_length(input: &str, quote: char, backslash_escapes: bool) -> Option<usize> {
+    let mut chars = input.char_indices().skip(1).peekable();
+    while let Some((i, c)) = chars.next() {
+        if backslash_escapes && c == '\\' {
+            chars.next();
+        } else if c == quote {
+            match chars.peek() {
+                Some((_, next)) if *next == quote => {
+                    chars.next();
+                }
+                _ => return Some(i + c.len_utf8()),
+            }
+        }
+    }
+    None
+}
+
+/// Returns the length of a block comment, PostgreSQL allowing them to be nested
+///
+/// None is returned if the comment isn't closed.
+fn block_comment_length(input: &str, dialect: Dialect) -> Option<usize> {
+    let mut depth: usize = 0;
+    let mut i: usize = 0;
+    while i < input.len() {
+        if input[i..].starts_with("/*") && (depth == 0 || dialect == Dialect::PostgreSql) {
+            depth += 1;
+            i += 2;
+        } else if input[i..].starts_with("*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return Some(i);
+            }
+        } else {
+            i += input[i..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    None
+}
+
+/// Returns the opening tag of a dollar quoted string, such as `$$` or `$body$`
+///
+/// None is returned if the input doesn't start with a dollar quote.
+fn dollar_quote_tag(input: &str) -> Option<&str> {
+    let tag: &str = &input[..input[1..].find('$')? + 2];
+    let tag_name: &str = &tag[1..tag.len() - 1];
+    if tag_name.starts_with(|c: char| c.is_ascii_digit())
+        || !tag_name.chars().all(|c| c.is_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+    Some(tag)
+}
+
+/// Split the given input into tokens
+///
+/// The comments, string literals and quoted identifiers are recognized following the rules
+/// of the given dialect, so that their content is never mistaken for SQL.
+///
+/// # Arguments
+///
+/// * `data` - The SQL content as a string
+/// * `dialect` - The SQL dialect of the input
+pub(crate) fn tokenize(data: &str, dialect: Dialect) -> Vec<Token<'_>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut offset: usize = 0;
+    while let Some(c) = data[offset..].chars().next() {
+        let rest: &str = &data[offset..];
+        // The length of the token, None if it isn't closed
+        let (kind, length): (TokenKind, Option<usize>) = if c.is_whitespace() {
+            (
+                TokenKind::Whitespace,
+                Some(
+                    rest.find(|c: char| !c.is_whitespace())
+                        .unwrap_or(rest.len()),
+                ),
+            )
+        } else if rest.starts_with("--") || (c == '#' && dialect.has_hash_comments()) {
+            (
+                TokenKind::Comment,
+                Some(rest.find('\n').unwrap_or(rest.len())),
+            )
+        } else if rest.starts_with("/*") {
+            (TokenKind::Comment, block_comment_length(rest, dialect))
+        } else if c == '\'' {
+            // The escape strings of PostgreSQL are prefixed by an E
+            let escape_string: bool = tokens.last().is_some_and(|token: &Token| {
+                token.kind == TokenKind::Word
+                    && token.text.eq_ignore_ascii_case("E")
+                    && token.offset + token.text.len() == offset
+            });
+            (
+                TokenKind::String,
+                quoted_length(rest, c, dialect == Dialect::MySql || escape_string),
+            )
+        } else if c == '"' || c == '`' {
+            (TokenKind::QuotedIdentifier, quoted_length(rest, c, false))
+        } else if c == '[' && dialect != Dialect::PostgreSql {
+            (TokenKind::QuotedIdentifier, rest.find(']').map(|i| i + 1))
+        } else if let Some(tag) = match c == '$' && dialect == Dialect::PostgreSql {
+            true => dollar_quote_tag(rest),
+            false => None,
+        } {
+            (
+                TokenKind::String,
+                rest[tag.len()..]
+                    .find(tag)
+                    .map(|i| tag.len() + i + tag.len()),
+            )
+        } else if is_word_char(c) {
+            (
+                TokenKind::Word,
+                Some(rest.find(|c: char| !is_word_char(c)).unwrap_or(rest.len())),
+            )
+        } else {
+            (TokenKind::Symbol, Some(c.len_utf8()))
+        };
+        let closed: bool = length.is_some();
+        let length: usize = length.unwrap_or(rest.len());
+        tokens.push(Token {
+            kind,
+            text: &rest[..length],
+            offset,
+            closed,
+        });
+        offset += length;
+    }
+    tokens
+}
+
+/// Build a statement from its tokens, blanking out its comments
+///
+/// # Arguments
+///
+/// * `tokens` - The tokens of the statement, the last one being cut at the end of the statement
+/// * `end` - The position of the end of the statement in the input, in bytes
+fn build_statement(tokens: &[Token], end: usize) -> Statement {
+    let mut content: String = String::new();
+    for token in tokens.iter().filter(|token| token.offset < end) {
+        match token.kind {
+            TokenKind::Comment => content.extend(token.text.chars().map(|c| match c {
+                '\n' => "\n".to_string(),
+                _ => " ".repeat(c.len_utf8()),
+            })),
+            // The delimiter can end a word, such as END$$
+            _ => content.push_str(&token.text[..token.text.len().min(end - token.offset)]),
+        }
+    }
+    Statement {
+        content,
+        offset: tokens.first().map_or(end, |token| token.offset),
+    }
+}
+
+/// Whether a statement can start with the given token
+fn is_statement_start(token: &Token) -> bool {
+    match token.kind {
+        TokenKind::Word => STATEMENT_KEYWORDS
+            .iter()
+            .any(|keyword| token.text.eq_ignore_ascii_case(keyword)),
+        _ => token.is_symbol('('),
+    }
+}
+
+/// Split the given input into statements
+///
+/// The statements are delimited by semicolons, unless the delimiter is changed with the
+/// `DELIMITER` command of MySQL. The empty statements and the meta-commands of psql are
+/// ignored. The statements that don't start with a known keyword are reported as diagnostics,
+/// as well as a string literal, a quoted identifier or a comment left open at the end of the
+/// input.
+///
+/// # Arguments
+///
+/// * `data` - The SQL content as a string
+/// * `dialect` - The SQL dialect of the input
+/// * `diagnostics` - The diagnostics found so far
+pub(crate) fn split_statements(
+    data: &str,
+    dialect: Dialect,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<Statement> {
+    let tokens: Vec<Token> = tokenize(data, dialect);
+    let line_end = |offset: usize| data[offset..].find('\n').map_or(data.len(), |i| offset + i);
+    let mut statements: Vec<Statement> = Vec::new();
+    let mut delimiter: String = String::from(";");
+    // Index of the first token of the current statement
+    let mut start: Option<usize> = None;
+    let mut i: usize = 0;
+    while let Some(token) = tokens.get(i) {
+        // Position until which the tokens are part of a delimiter
+        let mut skip_until: Option<usize> = None;
+        // Position of the delimiter, the delimiters made of word characters such as $$ being
+        // part of the word tokens
+        let delimiter_offset: Option<usize> = match token.kind {
+            TokenKind::Symbol => {
+                Some(token.offset).filter(|offset| data[*offset..].starts_with(delimiter.as_str()))
+            }
+            TokenKind::Word => token
+                .text
+                .find(delimiter.as_str())
+                .map(|i| token.offset + i),
+            _ => None,
+        };
+        match token.kind {
+            TokenKind::Whitespace | TokenKind::Comment => (),
+            TokenKind::Word
+                if start.is_none()
+                    && dialect == Dialect::MySql
+                    && token.text.eq_ignore_ascii_case("DELIMITER") =>
+            {
+                let line_end: usize = line_end(token.offset);
+                let new_delimiter: &str = data[token.offset + token.text.len()..line_end].trim();
+                if !new_delimiter.is_empty() {
+                    delimiter = new_delimiter.to_string();
+                }
+                skip_until = Some(line_end);
+            }
+            // The meta-commands of psql, such as \connect, last until the end of the line
+            TokenKind::Symbol
+                if start.is_none() && dialect == Dialect::PostgreSql && token.is_symbol('\\') =>
+            {
+                skip_until = Some(line_end(token.offset));
+            }
+            _ if delimiter_offset.is_some() => {
+                let end: usize = delimiter_offset.unwrap_or(token.offset);
+                if start.is_none() && end > token.offset {
+                    start = Some(i);
+                }
+                if let Some(start) = start {
+                    statements.push(build_statement(&tokens[start..=i], end));
+                }
+                start = None;
+                skip_until = Some(end + delimiter.len());
+            }
+            _ => {
+                if start.is_none() {
+                    start = Some(i);
+                }
+            }
+        }
+        if start == Some(i) && !is_statement_start(token) {
+            diagnostics.push(Diagnostic::new(
+                data,
+                token.offset,
+                "The statement couldn't be classified",
+            ));
+        }
+        i += 1;
+        if let Some(end) = skip_until {
+            while tokens.get(i).is_some_and(|token| token.offset < end) {
+                i += 1;
+            }
+        }
+    }
+    if let Some(start) = start {
+        statements.push(build_statement(&tokens[start..], data.len()));
+    }
+    if let Some(token) = tokens.last().filter(|token| !token.closed) {
+        let message: &str = match token.kind {
+            TokenKind::String => "The string literal isn't closed",
+            TokenKind::QuotedIdentifier => "The quoted identifier isn't closed",
+            _ => "The comment isn't closed",
+        };
+        diagnostics.push(Diagnostic::new(data, token.offset, message));
+    }
+    statements
+}
+
+/// Split the given content at the comas that aren't enclosed in parenthesis, strings or
+/// quoted identifiers
+///
+/// The parts only made of whitespaces are ignored, the others are returned along with their
+/// position in the content, in bytes.
+///
+/// # Arguments
+///
+/// * `content` - The content to split, such as the body of a table
+/// * `dialect` - The SQL dialect of the input
+pub(crate) fn split_at_comas(content: &str, dialect: Dialect) -> Vec<(usize, &str)> {
+    let mut parts: Vec<(usize, &str)> = Vec::new();
+    let mut depth: usize = 0;
+    let mut start: usize = 0;
+    for token in tokenize(content, dialect) {
+        if token.is_symbol('(') {
+            depth += 1;
+        } else if token.is_symbol(')') {
+            depth = depth.saturating_sub(1);
+        } else if token.is_symbol(',') && depth == 0 {
+            parts.push((start, &content[start..token.offset]));
+            start = token.offset + 1;
+        }
+    }
+    parts.push((start, &content[start..]));
+    parts.retain(|(_, part)| !part.trim().is_empty());
+    parts
+}
+
+/// Returns the given content until the parenthesis closing the one opened before it
+///
+/// If the parenthesis is never closed, the whole content is returned.
+///
+/// # Arguments
+///
+/// * `content` - The content following an opening parenthesis
+/// * `dialect` - The SQL dialect of the input
+pub(crate) fn get_enclosed_content(content: &str, dialect: Dialect) -> &str {
+    let mut depth: usize = 0;
+    for token in tokenize(content, dialect) {
+        if token.is_symbol('(') {
+            depth += 1;
+        } else if token.is_symbol(')') {
+            if depth == 0 {
+                return &content[..token.offset];
+            }
+            depth -= 1;
+        }
+    }
+    content
+}
+
+/// Functions whose arguments can contain a `FROM` that doesn't refer to a table
+const FUNCTIONS_WITH_FROM: [&str; 6] = [
+    "EXTRACT",
+    "SUBSTRING",
+    "SUBSTR",
+    "TRIM",
+    "OVERLAY",
+    "POSITION",
+];
+
+/// Returns the tokens of the content that aren't whitespaces or comments
+fn significant_tokens(content: &str, dialect: Dialect) -> Vec<Token<'_>> {
+    tokenize(content, dialect)
+        .into_iter()
+        .filter(|token| !matches!(token.kind, TokenKind::Whitespace | TokenKind::Comment))
+        .collect()
+}
+
+/// Returns the name of an identifier token, without its enclosing characters
+fn unquote(token: &Token) -> String {
+    match token.kind {
+        TokenKind::QuotedIdentifier if token.text.len() >= 2 => {
+            token.text[1..token.text.len() - 1].to_string()
+        }
+        _ => token.text.to_string(),
+    }
+}
+
+/// Whether the token can be the name of a table or a column
+fn is_identifier(token: &Token) -> bool {
+    matches!(token.kind, TokenKind::Word | TokenKind::QuotedIdentifier)
+}
+
+/// Returns the select list of a query, being the part between its first `SELECT` and the
+/// `FROM` following it
+///
+/// None is returned if the query doesn't select anything.
+///
+/// # Arguments
+///
+/// * `query` - The query, such as the body of a view
+/// * `dialect` - The SQL dialect of the input
+pub(crate) fn get_select_list(query: &str, dialect: Dialect) -> Option<&str> {
+    let tokens: Vec<Token> = significant_tokens(query, dialect);
+    let (position, select): (usize, &Token) = tokens.iter().enumerate().find(|(_, token)| {
+        token.kind == TokenKind::Word && token.text.eq_ignore_ascii_case("SELECT")
+    })?;
+    // The modifiers of the select aren't part of the list
+    let start: &Token = tokens[position + 1..]
+        .iter()
+        .find(|token| {
+            !(token.kind == TokenKind::Word
+                && ["DISTINCT", "ALL", "DISTINCTROW"]
+                    .iter()
+                    .any(|modifier| token.text.eq_ignore_ascii_case(modifier)))
+        })
+        .unwrap_or(select);
+    let mut depth: usize = 0;
+    for token in tokens[position + 1..].iter() {
+        if token.is_symbol('(') {
+            depth += 1;
+        } else if token.is_symbol(')') {
+            if depth == 0 {
+                return Some(&query[start.offset..token.offset]);
+            }
+            depth -= 1;
+        } else if depth == 0
+            && token.kind == TokenKind::Word
+            && token.text.eq_ignore_ascii_case("FROM")
+        {
+            return Some(&query[start.offset..token.offset]);
+        }
+    }
+    Some(&query[start.offset..])
+}
+
+/// Returns the name of an item of a select list, being its alias if it has one
+///
+/// The columns keep their name without the table they belong to, while the other
+/// expressions are returned as they are written.
+///
+/// # Arguments
+///
+/// * `item` - The item of the select list
+/// * `dialect` - The SQL dialect of the input
+pub(crate) fn get_select_item_name(item: &str, dialect: Dialect) -> String {
+    let tokens: Vec<Token> = significant_tokens(item, dialect);
+    match tokens.as_slice() {
+        [.., keyword, alias]
+            if keyword.kind == TokenKind::Word
+                && keyword.text.eq_ignore_ascii_case("AS")
+                && is_identifier(alias) =>
+        {
+            unquote(alias)
+        }
+        // A column, qualified or not
+        [first, rest @ ..]
+            if is_identifier(first)
+                && rest.chunks(2).all(|pair| match pair {
+                    [dot, name] => dot.is_symbol('.') && is_identifier(name),
+                    _ => false,
+                }) =>
+        {
+            unquote(tokens.last().unwrap_or(first))
+        }
+        // An expression followed by its alias, without the AS keyword
+        [.., previous, alias]
+            if is_identifier(alias)
+                && !alias.text.eq_ignore_ascii_case("END")
+                && (previous.is_symbol(')') || is_identifier(previous)) =>
+        {
+            unquote(alias)
+        }
+        _ => item.trim().to_string(),
+    }
+}
+
+/// Returns the tables a query selects from, found after its `FROM` and `JOIN` keywords
+///
+/// The tables are returned once, in the order they first appear, prefixed by their schema if
+/// declared.
+///
+/// # Arguments
+///
+/// * `query` - The query, such as the body of a view
+/// * `dialect` - The SQL dialect of the input
+pub(crate) fn get_referenced_tables(query: &str, dialect: Dialect) -> Vec<String> {
+    let tokens: Vec<Token> = significant_tokens(query, dialect);
+    let mut tables: Vec<String> = Vec::new();
+    // Whether each opened parenthesis holds the arguments of a function using FROM
+    let mut parenthesis: Vec<bool> = Vec::new();
+    // Depth of the FROM clause being read, whose tables can be separated by comas
+    let mut from_depth: Option<usize> = None;
+    let mut i: usize = 0;
+    while let Some(token) = tokens.get(i) {
+        i += 1;
+        let is_keyword = |keywords: &[&str]| {
+            token.kind == TokenKind::Word
+                && keywords
+                    .iter()
+                    .any(|keyword| token.text.eq_ignore_ascii_case(keyword))
+        };
+        if token.is_symbol('(') {
+            parenthesis.push(
+                i >= 2 && {
+                    let previous: &Token = &tokens[i - 2];
+                    previous.kind == TokenKind::Word
+                        && FUNCTIONS_WITH_FROM
+                            .iter()
+                            .any(|function| previous.text.eq_ignore_ascii_case(function))
+                },
+            );
+            continue;
+        } else if token.is_symbol(')') {
+            parenthesis.pop();
+            if from_depth.is_some_and(|depth| depth > parenthesis.len()) {
+                from_depth = None;
+            }
+            continue;
+        } else if is_keyword(&["FROM", "JOIN"]) && parenthesis.last() != Some(&true) {
+            from_depth = Some(parenthesis.len());
+        } else if !(token.is_symbol(',') && from_depth == Some(parenthesis.len())) {
+            if is_keyword(&[
+                "WHERE",
+                "GROUP",
+                "ORDER",
+                "HAVING",
+                "LIMIT",
+                "WINDOW",
+                "UNION",
+                "EXCEPT",
+                "INTERSECT",
+                "SELECT",
+            ]) && from_depth == Some(parenthesis.len())
+            {
+                from_depth = None;
+            }
+            continue;
+        }
+        // The joined tables can be enclosed in parenthesis
+        while tokens.get(i).is_some_and(|token| token.is_symbol('(')) {
+            parenthesis.push(false);
+            i += 1;
+        }
+        let mut name: String = match tokens.get(i) {
+            Some(token)
+                if is_identifier(token)
+                    && !(token.kind == TokenKind::Word
+                        && ["SELECT", "LATERAL", "VALUES", "WITH"]
+                            .iter()
+                            .any(|keyword| token.text.eq_ignore_ascii_case(keyword))) =>
+            {
+                unquote(token)
+            }
+            _ => continue,
+        };
+        i += 1;
+        while let (Some(dot), Some(part)) = (tokens.get(i), tokens.get(i + 1)) {
+            if !dot.is_symbol('.') || !is_identifier(part) {
+                break;
+            }
+            name = format!("{}.{}", name, unquote(part));
+            i += 2;
+        }
+        // The functions returning tables aren't tables
+        if !tokens.get(i).is_some_and(|token| token.is_symbol('(')) && !tables.contains(&name) {
+            tables.push(name);
+        }
+    }
+    tables
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Returns the kinds and texts of the tokens that aren't whitespaces
+    fn describe(data: &str, dialect: Dialect) -> Vec<(TokenKind, &str)> {
+        tokenize(data, dialect)
+            .into_iter()
+            .filter(|token| token.kind != TokenKind::Whitespace)
+            .map(|token| (token.kind, token.text))
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            describe("CREATE TABLE `foo` (bar INT);", Dialect::MySql),
+            vec![
+                (TokenKind::Word, "CREATE"),
+                (TokenKind::Word, "TABLE"),
+                (TokenKind::QuotedIdentifier, "`foo`"),
+                (TokenKind::Symbol, "("),
+                (TokenKind::Word, "bar"),
+                (TokenKind::Word, "INT"),
+                (TokenKind::Symbol, ")"),
+                (TokenKind::Symbol, ";"),
+            ],
+            "statement"
+        );
+        assert_eq!(
+            describe("'it''s' 'a\\'b' \"x\"\"y\"", Dialect::MySql),
+            vec![
+                (TokenKind::String, "'it''s'"),
+                (TokenKind::String, "'a\\'b'"),
+                (TokenKind::QuotedIdentifier, "\"x\"\"y\""),
+            ],
+            "escaped quotes"
+        );
+        assert_eq!(
+            describe("'a\\' 'b'", Dialect::PostgreSql),
+            vec![(TokenKind::String, "'a\\'"), (TokenKind::String, "'b'")],
+            "no backslash escapes in standard strings"
+        );
+        assert_eq!(
+            describe("E'a\\'b'", Dialect::PostgreSql),
+            vec![(TokenKind::Word, "E"), (TokenKind::String, "'a\\'b'")],
+            "backslash escapes in escape strings"
+        );
+        assert_eq!(
+            describe(
+                "AS $$ SELECT ';'; $$; $body$ x $$ y $body$ $1",
+                Dialect::PostgreSql
+            ),
+            vec![
+                (TokenKind::Word, "AS"),
+                (TokenKind::String, "$$ SELECT ';'; $$"),
+                (TokenKind::Symbol, ";"),
+                (TokenKind::String, "$body$ x $$ y $body$"),
+                (TokenKind::Word, "$1"),
+            ],
+            "dollar quotes"
+        );
+        assert_eq!(
+            describe("[my table] text[]", Dialect::Sqlite),
+            vec![
+                (TokenKind::QuotedIdentifier, "[my table]"),
+                (TokenKind::Word, "text"),
+                (TokenKind::QuotedIdentifier, "[]"),
+            ],
+            "brackets"
+        );
+        assert_eq!(
+            describe("'unclosed", Dialect::MySql),
+            vec![(TokenKind::String, "'unclosed")],
+            "unclosed string"
+        );
+    }
+
+    #[test]
+    fn test_tokenize_comments() {
+        for (input, dialect, comment) in [
+            (
+                "-- This is a single-line comment",
+                Dialect::MySql,
+                "-- This is a single-line comment",
+            ),
+            (
+                "SELECT * FROM users; -- This is another comment",
+                Dialect::MySql,
+                "-- This is another comment",
+            ),
+            (
+                "# This is a comment with hash",
+                Dialect::MySql,
+                "# This is a comment with hash",
+            ),
+            (
+                "SELECT * FROM products; # End of query",
+                Dialect::MySql,
+                "# End of query",
+            ),
+            (
+                "/* This is a\nmulti-line comment */",
+                Dialect::MySql,
+                "/* This is a\nmulti-line comment */",
+            ),
+            (
+                "SELECT * FROM orders; /* Comment within SQL */",
+                Dialect::Sqlite,
+                "/* Comment within SQL */",
+            ),
+            ("/*\n   \n*/", Dialect::MySql, "/*\n   \n*/"),
+            (
+                "/* a /* b */ c */",
+                Dialect::PostgreSql,
+                "/* a /* b */ c */",
+            ),
+            ("/* a /* b */ c */", Dialect::MySql, "/* a /* b */"),
+            ("-- ", Dialect::MySql, "-- "),
+        ] {
+            let comments: Vec<&str> = tokenize(input, dialect)
+                .into_iter()
+                .filter(|token| token.kind == TokenKind::Comment)
+                .map(|token| token.text)
+                .collect();
+            assert_eq!(comments, vec![comment], "{}", input);
+        }
+
+        for (input, dialect) in [
+            ("SELECT * FROM users;", Dialect::MySql),
+            ("SELECT '-- not a comment', '#nor this';", Dialect::MySql),
+            ("SELECT `/* nor this */`;", Dialect::MySql),
+            ("SELECT #{hash} FROM foo;", Dialect::PostgreSql),
+            ("SELECT $$ -- nor this $$;", Dialect::PostgreSql),
+        ] {
+            assert!(
+                tokenize(input, dialect)
+                    .iter()
+                    .all(|token| token.kind != TokenKind::Comment),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_statements() {
+        let data: &str =
+            "CREATE TABLE a (b VARCHAR(2) DEFAULT ';'); -- c;\n\n;CREATE TABLE d (e INT)";
+        let statements: Vec<Statement> = split_statements(data, Dialect::MySql, &mut Vec::new());
+        assert_eq!(statements.len(), 2, "statements");
+        assert_eq!(
+            statements[0].get_content(),
+            "CREATE TABLE a (b VARCHAR(2) DEFAULT ';')",
+            "string containing a semicolon"
+        );
+        assert_eq!(statements[0].get_offset(), 0, "offset");
+        assert_eq!(
+            statements[1].get_content(),
+            "CREATE TABLE d (e INT)",
+            "last statement"
+        );
+        assert_eq!(
+            &data[statements[1].get_offset()..],
+            "CREATE TABLE d (e INT)",
+            "offset"
+        );
+
+        let statements: Vec<Statement> =
+            split_statements("SELECT /* a;\nb */ 1;", Dialect::MySql, &mut Vec::new());
+        assert_eq!(
+            statements[0].get_content(),
+            "SELECT      \n     1",
+            "comments blanked out"
+        );
+
+        let statements: Vec<Statement> = split_statements(
+            "DELIMITER ;;\nCREATE TRIGGER t BEFORE INSERT ON a FOR EACH ROW BEGIN SET NEW.b = 1; END ;;\nDELIMITER ;\nCREATE TABLE a (b INT);",
+            Dialect::MySql,
+            &mut Vec::new(),
+        );
+        assert_eq!(statements.len(), 2, "delimiter");
+        assert!(
+            statements[0].get_content().ends_with("SET NEW.b = 1; END "),
+            "delimiter"
+        );
+        assert_eq!(
+            statements[1].get_content(),
+            "CREATE TABLE a (b INT)",
+            "delimiter"
+        );
+
+        let statements: Vec<Statement> = split_statements(
+            "CREATE TABLE a (b INT);\nDELIMITER $$\nCREATE PROCEDURE p() BEGIN SELECT 1; END $$\nCREATE PROCEDURE q() BEGIN SELECT 2; END$$\nDELIMITER ;\nCREATE TABLE c (d INT);",
+            Dialect::MySql,
+            &mut Vec::new(),
+        );
+        assert_eq!(statements.len(), 4, "dollar delimiter");
+        assert!(
+            statements[1].get_content().ends_with("SELECT 1; END "),
+            "dollar delimiter"
+        );
+        assert!(
+            statements[2].get_content().ends_with("SELECT 2; END"),
+            "dollar delimiter ending a word"
+        );
+        assert_eq!(
+            statements[3].get_content(),
+            "CREATE TABLE c (d INT)",
+            "dollar delimiter"
+        );
+    }
+
+    #[test]
+    fn test_split_statements_diagnostics() {
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        let statements: Vec<Statement> = split_statements(
+            "CREATE TABLE a (b INT);\nMy table;\n\\connect foo\nCREATE TABLE c (d INT);",
+            Dialect::PostgreSql,
+            &mut diagnostics,
+        );
+        assert_eq!(statements.len(), 3, "statements");
+        assert_eq!(diagnostics.len(), 1, "unclassified statement");
+        assert_eq!(diagnostics[0].get_line(), 2, "unclassified statement");
+        assert_eq!(
+            diagnostics[0].get_message(),
+            "The statement couldn't be classified",
+            "unclassified statement"
+        );
+
+        for (input, message) in [
+            (
+                "CREATE TABLE a (b INT);\nINSERT INTO a VALUES ('b);\nCREATE TABLE c (d INT);",
+                "The string literal isn't closed",
+            ),
+            (
+                "CREATE TABLE a (b INT);\nCREATE TABLE `c (d INT);",
+                "The quoted identifier isn't closed",
+            ),
+            (
+                "CREATE TABLE a (b INT);\n/* CREATE TABLE c (d INT);",
+                "The comment isn't closed",
+            ),
+        ] {
+            let mut diagnostics: Vec<Diagnostic> = Vec::new();
+            split_statements(input, Dialect::MySql, &mut diagnostics);
+            assert_eq!(diagnostics.len(), 1, "{}", input);
+            assert_eq!(diagnostics[0].get_line(), 2, "{}", input);
+            assert_eq!(diagnostics[0].get_message(), message, "{}", input);
+        }
+
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        split_statements(
+            "CREATE TABLE a (b TEXT DEFAULT 'c''d');\n-- e",
+            Dialect::MySql,
+            &mut diagnostics,
+        );
+        assert!(diagnostics.is_empty(), "closed tokens");
+    }
+
+    #[test]
+    fn test_split_at_comas() {
+        assert_eq!(
+            split_at_comas(
+                "a INT DEFAULT ',', b DECIMAL(10, 2), `c,d` TEXT COMMENT 'e(', ",
+                Dialect::MySql
+            ),
+            vec![
+                (0, "a INT DEFAULT ','"),
+                (18, " b DECIMAL(10, 2)"),
+                (36, " `c,d` TEXT COMMENT 'e('")
+            ],
+            "comas"
+        );
+        assert_eq!(
+            split_at_comas("a", Dialect::MySql),
+            vec![(0, "a")],
+            "single"
+        );
+    }
+
+    #[test]
+    fn test_get_enclosed_content() {
+        assert_eq!(
+            get_enclosed_content(
+                "a INT, b CHECK (b > ')') ) INHERITS (c)",
+                Dialect::PostgreSql
+            ),
+            "a INT, b CHECK (b > ')') ",
+            "enclosed"
+        );
+        assert_eq!(
+            get_enclosed_content("a INT", Dialect::PostgreSql),
+            "a INT",
+            "never closed"
+        );
+    }
+
+    #[test]
+    fn test_get_select_list() {
+        assert_eq!(
+            get_select_list(
+                "SELECT DISTINCT a, MAX(b) AS b, EXTRACT(YEAR FROM c) FROM t",
+                Dialect::MySql
+            ),
+            Some("a, MAX(b) AS b, EXTRACT(YEAR FROM c) "),
+            "select list"
+        );
+        assert_eq!(
+            get_select_list("(SELECT 1)", Dialect::MySql),
+            Some("1"),
+            "enclosed query"
+        );
+        assert_eq!(
+            get_select_list("VALUES (1)", Dialect::MySql),
+            None,
+            "no select"
+        );
+    }
+
+    #[test]
+    fn test_get_select_item_name() {
+        for (item, name) in [
+            ("emp_no", "emp_no"),
+            ("l.from_date", "from_date"),
+            ("public.\"Employee\".\"Name\"", "Name"),
+            ("MAX(from_date) AS from_date", "from_date"),
+            ("count(*) total", "total"),
+            (
+                "CASE WHEN a THEN 1 ELSE 0 END",
+                "CASE WHEN a THEN 1 ELSE 0 END",
+            ),
+            ("a + b", "a + b"),
+            ("*", "*"),
+        ] {
+            assert_eq!(
+                get_select_item_name(item, Dialect::PostgreSql),
+                name,
+                "{}",
+                item
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_referenced_tables() {
+        assert_eq!(
+            get_referenced_tables(
+                "SELECT l.emp_no, EXTRACT(YEAR FROM d.from_date)
+                FROM (public.dept_emp d
+                JOIN public.dept_emp_latest_date l ON d.emp_no = l.emp_no)
+                LEFT OUTER JOIN `employees` AS e USING (emp_no), titles t, salaries
+                WHERE e.emp_no IN (SELECT emp_no FROM dept_manager)
+                AND d.dept_no NOT IN (SELECT * FROM generate_series(1, 3))",
+                Dialect::MySql
+            ),
+            vec![
+                "public.dept_emp",
+                "public.dept_emp_latest_date",
+                "employees",
+                "titles",
+                "salaries",
+                "dept_manager"
+            ],
+            "tables"
+        );
+    }
+}
@@ -5,7 +5,7 @@
 //! <h4 align="center">This library contains all the tools used to transform your SQL input into a DOT one.</h4>
 //! <p align="justify">Doteur is a CLI (Command Line Interface) tool that has for purpose to render the SQL schemas into good looking graphs. This will help you to easily understand the structure of a large database and understand what happens behind the scenes of your project.</p>
 //! Besides, you will be able to use the large panel of features to either sort the tables you want to visualize or render with a different color scheme for instance.
-//! So far the tool handles both the MySQL and SQLite syntaxes, but it is planned to handle the Postgre one as soon as the formers will be considered as stable. The input of the tool can be either a sql file export, or given the version you downloaded, connect to either a MySQL running instance or an existing SQLite database.
+//! So far the tool handles the MySQL, SQLite and PostgreSQL syntaxes, including the exports made with `mysqldump` and `pg_dump`. The input of the tool can be either a sql file export, or given the version you downloaded, connect to either a MySQL running instance or an existing SQLite database.
 //! The tool has been developed on Linux, but is also available for Windows 10 and 11 and macOS.
 //! <br/>
 //! <p>Useful links :</p>
@@ -15,16 +15,34 @@
 //! <li><a href="https://docker.com/nag763/doteur">Docker tool</a></li>
 //! </ul>
 
+/// Module used to describe the parts of an input that couldn't be understood
+///
+/// The diagnostics are located at the line and column of the input where the issue is
+pub mod diagnostic;
+/// Module used to detect and describe the SQL dialect of an input
+///
+/// The dialect can either be given explicitly or detected from the input
+pub mod dialect;
 #[cfg(feature = "mysql_addons")]
 /// Module used to connect to a remote MySQL running database instance
 ///
 /// This module is only available with the `mysql_addons` feature
 pub mod mysql_tools;
+#[cfg(feature = "postgres_addons")]
+/// Module used to connect to a remote PostgreSQL running database instance
+///
+/// This module is only available with the `postgres_addons` feature
+pub mod postgres_tools;
 /// Module used to filter the tables to render
 ///
 /// A restriction can either be inclusive, meaning that only the tables that matche the restriction
 /// are rendered, or exclusive, meaning that only the tables that don't match the restrictions will be rendered
 pub mod restriction;
+/// Module containing the parsed representation of a SQL input
+///
+/// The schema holds the tables, their columns and the relations between them, and is what
+/// every renderer is built from
+pub mod schema;
 #[cfg(feature = "sqlite_addons")]
 /// Module used to connect to a SQLite database
 ///
@@ -39,20 +57,35 @@ mod add_traits;
 mod dot_structs;
 /// Module containing the errors thrown by the core libraries
 mod errors;
-
-use std::borrow::Cow;
+/// Module containing the lexer splitting the SQL inputs into tokens and statements
+mod lexer;
+/// Module containing the renderers of the schema to the formats other than DOT
+mod renderers;
 
 use crate::add_traits::{Replacable, SplitVec, Trim};
+use crate::diagnostic::Diagnostic;
+use crate::dialect::Dialect;
 use crate::errors::DoteurCoreError;
+use crate::lexer::{
+    get_enclosed_content, get_referenced_tables, get_select_item_name, get_select_list,
+    split_at_comas, split_statements, Statement,
+};
 use crate::restriction::Restriction;
+use crate::schema::{
+    Check, Column, Index, ReferentialAction, Relation, Schema, Table, Verbosity, View,
+};
 use crate::tools::detect_comas;
 
 use dot_structs::dot_file::DotFile;
-use dot_structs::dot_table::DotTable;
-use dot_structs::relation::Relation;
+use renderers::dbml::DbmlFile;
+use renderers::json::JsonFile;
+use renderers::mermaid::MermaidFile;
+use renderers::plantuml::PlantUmlFile;
+#[cfg(feature = "native_svg")]
+use renderers::svg::SvgFile;
 
 use log::{debug, error, info, warn};
-use regex::{Captures, Regex};
+use regex::{Captures, Match, Regex};
 
 #[macro_use]
 extern crate lazy_static;
@@ -82,36 +115,43 @@ macro_rules! unwrap_captures_name_as_str {
 
 lazy_static! {
     ///Get table name.
-    static ref RE_TABLE_NAME : Regex = Regex::new(r####"(?i)\s*CREATE\s*TABLE\s*(?:IF\s*NOT\s*EXISTS)?\s*(?:(?:\w+)\.)?(?P<table_name>(?:[`"\[]{1}[^`"\]]+[`"\]]{1})|(?:\w*))\s*\((?P<content>[^;]*)\)"####).unwrap();
+    static ref RE_TABLE_NAME : Regex = Regex::new(r####"(?i)^\s*CREATE\s*(?:(?:TEMP|TEMPORARY|UNLOGGED)\s+)?TABLE\s*(?:IF\s*NOT\s*EXISTS)?\s*(?:(?P<schema_name>[`"\[]?\w+[`"\]]?)\.)?(?P<table_name>(?:[`"\[]{1}[^`"\]]+[`"\]]{1})|(?:\w*))\s*\((?P<content>(?s:.*))\)"####).unwrap();
     ///Get column type
-    static ref RE_COL_TYPE : Regex = Regex::new(r####"(?i)\s*((?:FULLTEXT|SPATIAL)?\s+(?:INDEX|KEY|CHECK))|(?:CONSTRAINT\s*[`'"]\w*[`'"])?\s*(?P<key_type>UNIQUE|FOREIGN|PRIMARY)\s+"####).unwrap();
+    static ref RE_COL_TYPE : Regex = Regex::new(r####"(?i)\s*((?:FULLTEXT|SPATIAL)?\s+(?:INDEX|KEY))|(?:CONSTRAINT\s*[`'"]\w*[`'"])?\s*(?P<key_type>UNIQUE|FOREIGN|PRIMARY)\s+"####).unwrap();
     ///Get columns definitioon
     static ref RE_COL_DEF : Regex = Regex::new(r####"(?i)\s*(?P<col_name>(?:[`"\[]{1}[^`"\]]+[`"\]]{1})|(?:\w*))\s*(?P<col_def>.*)"####).unwrap();
     ///Check if input is a primary key
     static ref RE_PK_DEF : Regex = Regex::new(r####"(?i)PRIMARY\s*KEY\s*["`]?(?:\w*)[`"]?\s*\((?P<col_name>[^\)]+)\)"####).unwrap();
     ///Check if a PK is declared in the line
-    static ref RE_PK_IN_LINE : Regex = Regex::new(r####"(?i)\s*PRIMARY\s*KEY.*"####).unwrap();
+    static ref RE_PK_IN_LINE : Regex = Regex::new(r####"(?i)\s*PRIMARY\s*KEY\b(?:\s+(?:ASC|DESC)\b)?"####).unwrap();
     ///Check for the content in parenthesis.
-    static ref RE_FK_DEF : Regex = Regex::new(r####"(?i)FOREIGN\s*KEY\s*(?:(?:public|private).)?\((?P<table_key>[^\)]+)\)\s*REFERENCES\s*(?:(?:public|private).)?[`"'\[]?(?P<distant_table>\w*)["`'\]]?\s*\((?P<distant_key>[^\)]+)\)\s*(?:(?:ON\s*UPDATE\s*(?:(?:SET\s*\w*|\w*))\s*)?(?:ON\s*DELETE\s*)?(?P<on_delete>(SET\s*NULL|CASCADE|RESTRICT|NO\s*ACTION|SET\s*DEFAULT)))?"####).unwrap();
+    static ref RE_FK_DEF : Regex = Regex::new(r####"(?i)FOREIGN\s*KEY\s*(?:(?:public|private).)?\((?P<table_key>[^\)]+)\)\s*REFERENCES\s*(?:(?P<distant_schema>[`"\[]?\w+[`"\]]?)\.)?[`"'\[]?(?P<distant_table>\w*)["`'\]]?\s*\((?P<distant_key>[^\)]+)\)\s*(?:ON\s*UPDATE\s*(?P<on_update>SET\s*NULL|CASCADE|RESTRICT|NO\s*ACTION|SET\s*DEFAULT)\s*)?(?:ON\s*DELETE\s*(?P<on_delete>SET\s*NULL|CASCADE|RESTRICT|NO\s*ACTION|SET\s*DEFAULT)\s*)?(?:ON\s*UPDATE\s*(?P<trailing_on_update>SET\s*NULL|CASCADE|RESTRICT|NO\s*ACTION|SET\s*DEFAULT))?"####).unwrap();
+    ///Look after the statements declaring a table, understood or not
+    static ref RE_TABLE_DECLARATION : Regex = Regex::new(r####"(?i)^\s*CREATE\s+(?:\w+\s+)*TABLE\b"####).unwrap();
+    ///Check for the foreign keys declared along a column, the refered key being optional
+    static ref RE_INLINE_FK_DEF : Regex = Regex::new(r####"(?i)\bREFERENCES\s*(?:(?P<distant_schema>[`"\[]?\w+[`"\]]?)\.)?[`"'\[]?(?P<distant_table>\w*)["`'\]]?\s*(?:\((?P<distant_key>[^\)]+)\))?\s*(?:ON\s*UPDATE\s*(?P<on_update>SET\s*NULL|CASCADE|RESTRICT|NO\s*ACTION|SET\s*DEFAULT)\s*)?(?:ON\s*DELETE\s*(?P<on_delete>SET\s*NULL|CASCADE|RESTRICT|NO\s*ACTION|SET\s*DEFAULT)\s*)?(?:ON\s*UPDATE\s*(?P<trailing_on_update>SET\s*NULL|CASCADE|RESTRICT|NO\s*ACTION|SET\s*DEFAULT))?"####).unwrap();
+    ///Check for the indexes and unique constraints declared in a table or added to it
+    static ref RE_INDEX_DEF : Regex = Regex::new(r####"(?i)^\s*(?:ADD\s+)?(?:CONSTRAINT\s+(?P<constraint_name>[`"\[][^`"\]]+[`"\]]|\w+)\s+)?(?:(?P<unique>UNIQUE)(?:\s+(?:KEY|INDEX))?|(?:(?:FULLTEXT|SPATIAL)\s+)?(?:KEY|INDEX))\s*(?:(?P<index_name>[`"\[][^`"\]]+[`"\]]|\w+)\s*)?(?:USING\s+\w+\s*)?\((?P<columns>(?s:.*))"####).unwrap();
+    ///Check for the check constraints declared in a table or added to it
+    static ref RE_CHECK_DEF : Regex = Regex::new(r####"(?i)^\s*(?:ADD\s+)?(?:CONSTRAINT\s+(?P<constraint_name>[`"\[][^`"\]]+[`"\]]|\w+)\s+)?CHECK\s*\((?P<expression>(?s:.*))"####).unwrap();
+    ///Get the comment of a table, declared among its options
+    static ref RE_TABLE_COMMENT : Regex = Regex::new(r####"(?i)\bCOMMENT\s*=?\s*'(?P<comment>(?:[^']|'')*)'"####).unwrap();
+    ///Look after the comment statements on a table or a column
+    static ref RE_COMMENT_ON : Regex = Regex::new(r####"(?i)^\s*COMMENT\s+ON\s+(?P<kind>TABLE|COLUMN)\s+(?P<target>(?:[`"\[]?\w+[`"\]]?\s*\.\s*){0,2}[`"\[]?\w+[`"\]]?)\s+IS\s+(?:'(?P<comment>(?:[^']|'')*)'|NULL)"####).unwrap();
+    ///Look after the create index statements
+    static ref RE_CREATE_INDEX : Regex = Regex::new(r####"(?i)^\s*CREATE\s+(?P<unique>UNIQUE\s+)?(?:(?:CLUSTERED|NONCLUSTERED|FULLTEXT|SPATIAL)\s+)?INDEX\s+(?:CONCURRENTLY\s+)?(?:IF\s+NOT\s+EXISTS\s+)?(?:(?:[`"\[]?\w+[`"\]]?\.)?(?P<index_name>[`"\[][^`"\]]+[`"\]]|\w+)\s+)?ON\s+(?:ONLY\s+)?(?:(?P<schema_name>[`"\[]?\w+[`"\]]?)\.)?[`"\[]?(?P<table_name>\w+)[`"\]]?\s*(?:USING\s+\w+\s*)?\((?P<columns>(?s:.*))"####).unwrap();
+    ///Look after the statements declaring an index, understood or not
+    static ref RE_INDEX_DECLARATION : Regex = Regex::new(r####"(?i)^\s*CREATE\s+(?:\w+\s+)*INDEX\b"####).unwrap();
+    ///Get the column of an index, without its length or its order
+    static ref RE_INDEX_COLUMN : Regex = Regex::new(r####"^\s*(?P<col_name>[`"\[][^`"\]]+[`"\]]|[^\s(]+)(?:\s*\(\s*\d+\s*\))?\s*(?P<call>\()?"####).unwrap();
+    ///Look after the create view statements
+    static ref RE_VIEW : Regex = Regex::new(r####"(?i)^\s*CREATE\s+(?:OR\s+REPLACE\s+)?(?:(?:ALGORITHM\s*=\s*\w+|DEFINER\s*=\s*\S+|SQL\s+SECURITY\s+\w+|TEMP|TEMPORARY|RECURSIVE|MATERIALIZED)\s+)*VIEW\s+(?:IF\s+NOT\s+EXISTS\s+)?(?:[`"\[]?\w+[`"\]]?\.)?[`"\[]?(?P<view_name>\w+)[`"\]]?\s*(?:\((?P<columns>[^)]*)\)\s*)?(?:WITH\s*\([^)]*\)\s*)?AS\b(?P<query>(?s:.*))"####).unwrap();
+    ///Look after the statements declaring a view, understood or not
+    static ref RE_VIEW_DECLARATION : Regex = Regex::new(r####"(?i)^\s*CREATE\s+(?:\S+\s+)*?VIEW\s"####).unwrap();
     ///Look after alter table statements.
-    static ref RE_ALTERED_TABLE : Regex = Regex::new(r####"\s*(?i)ALTER\s*TABLE\s*(?:ONLY)?\s*['`"\[]?(?:(?:public|private).)?(?P<table_name>\w*)[`"'\]]?\s*(?P<altered_content>[^;]*)"####).unwrap();
-    ///Regex to remove comments
-    static ref RE_COMMENTS : Regex = Regex::new(r####"(--.*|#.*|/\*[^*/]*\*/)"####).unwrap();
-}
-
-/// Get the tables from the input
-///
-/// This method will check the data given on input and will return all the tables
-/// until the end of their declaration.
-///
-/// # Arguments
-///
-/// * `data` - The content where sql table are stored
-fn get_tables(data: &str) -> Vec<&str> {
-    RE_TABLE_NAME
-        .find_iter(data)
-        .map(|element| element.as_str())
-        .collect::<Vec<&str>>()
+    static ref RE_ALTERED_TABLE : Regex = Regex::new(r####"^\s*(?i)ALTER\s*TABLE\s*(?:ONLY\s+)?(?:IF\s+EXISTS\s+)?(?:(?P<schema_name>[`"\[]?\w+[`"\]]?)\.)?['`"\[]?(?P<table_name>\w*)[`"'\]]?\s*(?P<altered_content>(?s:.*?));?\s*$"####).unwrap();
+    ///Look after the default values set on alter table statements.
+    static ref RE_ALTERED_COLUMN_DEFAULT : Regex = Regex::new(r####"(?i)ALTER\s+(?:COLUMN\s+)?(?P<col_name>[`"\[]?\w+[`"\]]?)\s+SET\s+DEFAULT\s+(?P<default>.+)"####).unwrap();
 }
 
 /// Check if the given input contains sql tables
@@ -142,21 +182,30 @@ fn get_tables(data: &str) -> Vec<&str> {
 /// ```
 ///
 pub fn contains_sql_tables(data: &str) -> bool {
-    RE_TABLE_NAME.is_match(data)
+    split_statements(data, Dialect::detect(data), &mut Vec::new())
+        .iter()
+        .any(|statement| RE_TABLE_NAME.is_match(statement.get_content()))
 }
 
-/// Remove the SQL comments from an input
-fn remove_sql_comments(data: &str) -> Cow<'_, str> {
-    RE_COMMENTS.replace_all(data, "")
-}
-
-/// Convert a sql table to a dot table and store it in the given dot file
-fn convert_sql_table_to_dot(
-    input: &str,
+/// Convert a sql table to a table of the schema
+///
+/// The lines of the table that can't be understood are ignored and reported as diagnostics.
+///
+/// # Arguments
+///
+/// * `data` - The whole input, used to locate the diagnostics
+/// * `statement` - The statement declaring the table
+/// * `restrictions` - The list of filters we want to apply on the input
+/// * `dialect` - The SQL dialect of the input
+/// * `diagnostics` - The diagnostics found so far
+fn convert_sql_table(
+    data: &str,
+    statement: &Statement,
     restrictions: Option<&Restriction>,
-    dark_mode: bool,
-) -> Result<Option<(String, DotTable, Vec<Relation>)>, DoteurCoreError> {
-    let captures: Captures = RE_TABLE_NAME.captures(input).unwrap();
+    dialect: Dialect,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<Option<(String, Table, Vec<Relation>)>, DoteurCoreError> {
+    let captures: Captures = RE_TABLE_NAME.captures(statement.get_content()).unwrap();
 
     let table_name: String = unwrap_captures_name_as_str!(
         captures,
@@ -166,7 +215,7 @@ fn convert_sql_table_to_dot(
     .replace_enclosing()
     .trim_leading_trailing();
     info!(
-        "Starting to convert the SQL table {} into a DOT table",
+        "Starting to convert the SQL table {} into a schema table",
         table_name
     );
 
@@ -175,46 +224,83 @@ fn convert_sql_table_to_dot(
         return Ok(None);
     }
 
-    let attr_defs: String = unwrap_captures_name_as_str!(
-        captures,
-        "content",
-        "Regex error, the input is either not a sql table or isn't pared properly by the process"
-    )
-    .trim_leading_trailing();
-
-    let lines: Vec<&str> = match detect_comas(attr_defs.as_str()) {
-        Ok(v) => {
-            debug!(
-                "Table {} splitted correctly, {} unclosed comas found",
-                table_name,
-                v.len()
-            );
-            attr_defs.split_vec(v)
-        }
-        Err(e) => {
-            error!("Error in comas parsing for table : {0}\n{1}", table_name, e);
-            return Err(DoteurCoreError::user_input_malformed(
-                "Attributes malformed",
-            ));
+    // The table options declared after the columns, such as `INHERITS (...)` or `WITH (...)`,
+    // are not part of the table content.
+    let content: Match = match captures.name("content") {
+        Some(content) => content,
+        None => {
+            return Err(DoteurCoreError::regex_error(
+                "Regex error, the input is either not a sql table or isn't pared properly by the process",
+                file!(),
+                line!(),
+            ))
         }
     };
+    let attr_defs: &str = get_enclosed_content(content.as_str(), dialect);
+
+    let lines: Vec<(usize, &str)> = split_at_comas(attr_defs, dialect);
+    // Report an issue at the start of the given line
+    let mut report = |position: usize, line: &str, message: String| {
+        let offset: usize = statement.get_offset()
+            + content.start()
+            + position
+            + (line.len() - line.trim_start().len());
+        diagnostics.push(Diagnostic::new(data, offset, message.as_str()));
+    };
+    debug!(
+        "Table {} splitted correctly, {} attributes found",
+        table_name,
+        lines.len()
+    );
 
-    let mut dot_table: DotTable = DotTable::new(table_name.as_str(), dark_mode);
+    let mut table: Table = Table::new(table_name.as_str());
+    let schema_name: Option<&str> = captures.name("schema_name").map(|m| m.as_str());
+    table.set_schema(schema_name.map(|schema| schema.replace_enclosing().trim_leading_trailing()));
+    // The relations refer to the table along its schema until every table is known
+    let qualified_name: String = qualify(schema_name, table_name.as_str());
     let mut relations: Vec<Relation> = Vec::new();
 
-    for line in lines {
+    // The comment of the table is declared among the options following the columns
+    table.set_comment(
+        RE_TABLE_COMMENT
+            .captures(&statement.get_content()[content.start() + attr_defs.len()..])
+            .and_then(|captures| captures.name("comment"))
+            .map(|m| m.as_str().replace("''", "'")),
+    );
+
+    for (position, line) in lines {
+        // The check constraints aren't bound to a column
+        if let Some(check) = generate_check(line, dialect) {
+            debug!(
+                "Line {} has been found as a check constraint",
+                line.trim_leading_trailing()
+            );
+            table.add_check(check);
         // If column type is common attribute
-        if !RE_COL_TYPE.is_match(line) {
+        } else if !RE_COL_TYPE.is_match(line) {
             debug!(
                 "Line {} is an attribute definition",
                 line.trim_leading_trailing()
             );
-            match generate_attributes(&mut dot_table, line) {
-                Ok(col_name) => info!(
-                    "Attribute {} processed correctly and added to table {}",
-                    col_name, table_name
-                ),
-                Err(e) => error!("An error happened while processing line : {}", e),
+            match generate_attributes(&mut table, line, restrictions) {
+                Ok((col_name, relation)) => {
+                    info!(
+                        "Attribute {} processed correctly and added to table {}",
+                        col_name, table_name
+                    );
+                    relations.extend(relation);
+                }
+                Err(e) => {
+                    error!("An error happened while processing line : {}", e);
+                    report(
+                        position,
+                        line,
+                        format!(
+                            "The column of the table {} couldn't be understood",
+                            table_name
+                        ),
+                    );
+                }
             }
         // If column type is a relation or an index
         } else {
@@ -230,6 +316,16 @@ fn convert_sql_table_to_dot(
                 }
             };
 
+            // The indexes and the unique constraints are added to the table
+            if let Some(index) = generate_index(line, dialect) {
+                debug!(
+                    "Line {} has been found as an index definition",
+                    line.trim_leading_trailing()
+                );
+                table.add_index(index);
+                continue;
+            }
+
             let key_type: String = unwrap_captures_name_as_str!(col_type, "key_type", {
                 warn!("Key type isn't handled, line will be ignored");
                 continue;
@@ -241,7 +337,7 @@ fn convert_sql_table_to_dot(
                         "Line {} has been found as a foreign key def",
                         line.trim_leading_trailing()
                     );
-                    match generate_relations(table_name.as_str(), line, restrictions) {
+                    match generate_relations(qualified_name.as_str(), line, restrictions) {
                         Ok(v) => {
                             // If the relations matched the restrictions
                             if let Some(relation) = v {
@@ -250,19 +346,33 @@ fn convert_sql_table_to_dot(
                                 debug!("{} relations have been added following the processing of the line {}", relation.get_number_of_pairs_of_keys(), line.trim_leading_trailing());
                                 // And add the FK nature to the attributes in table
                                 for pair_key_refered in relation.get_pairs_of_keys() {
-                                    match dot_table.add_fk_nature_to_attribute(
+                                    match table.add_fk_nature_to_column(
                                         pair_key_refered.0.as_str(),
                                         relation.get_refered_table(),
                                         pair_key_refered.1.as_str(),
                                     ) {
-                                        Ok(_) => info!("Attribute {} of table {} has been detected as FK", pair_key_refered.0, table_name),
-                                        Err(e) => error!("An error happened while adding the FK nature of attribute {} to the table {} : {}", pair_key_refered.0, table_name, e)
+                                        Ok(_) => info!(
+                                            "Attribute {} of table {} has been detected as FK",
+                                            pair_key_refered.0, table_name
+                                        ),
+                                        Err(e) => {
+                                            error!("An error happened while adding the FK nature of attribute {} to the table {} : {}", pair_key_refered.0, table_name, e);
+                                            report(position, line, format!("The column {} of the foreign key isn't declared in the table {}", pair_key_refered.0, table_name));
+                                        }
                                     };
                                 }
                             }
                         }
                         Err(e) => {
-                            error!("An error happened while processing the foreign key: {}", e)
+                            error!("An error happened while processing the foreign key: {}", e);
+                            report(
+                                position,
+                                line,
+                                format!(
+                                    "The foreign key of the table {} couldn't be understood",
+                                    table_name
+                                ),
+                            );
                         }
                     }
                 }
@@ -272,13 +382,24 @@ fn convert_sql_table_to_dot(
                             "Line {} has been found as a primary key definition including an attribute definition",
                             line.trim_leading_trailing()
                         );
-                        match generate_attributes(&mut dot_table, line) {
-                            Ok(col_name) => info!(
-                                "PK {} with attribute definition added to table {}",
-                                col_name, table_name
-                            ),
+                        match generate_attributes(&mut table, line, restrictions) {
+                            Ok((col_name, relation)) => {
+                                info!(
+                                    "PK {} with attribute definition added to table {}",
+                                    col_name, table_name
+                                );
+                                relations.extend(relation);
+                            }
                             Err(e) => {
-                                error!("An error happened while processing primary key : {}", e)
+                                error!("An error happened while processing primary key : {}", e);
+                                report(
+                                    position,
+                                    line,
+                                    format!(
+                                        "The primary key of the table {} couldn't be understood",
+                                        table_name
+                                    ),
+                                );
                             }
                         }
                     } else {
@@ -286,27 +407,63 @@ fn convert_sql_table_to_dot(
                             "Line {} has been found as a primary key def",
                             line.trim_leading_trailing()
                         );
-                        match generate_primary(&mut dot_table, line) {
+                        match generate_primary(&mut table, line) {
                             Ok(m) => info!(
                                 "PK(s) processed correctly {} and added to the table {}",
                                 m, table_name
                             ),
                             Err(e) => {
-                                error!("An error happened while processing primary key : {}", e)
+                                error!("An error happened while processing primary key : {}", e);
+                                report(
+                                    position,
+                                    line,
+                                    format!(
+                                        "The primary key of the table {} refers to columns that aren't declared",
+                                        table_name
+                                    ),
+                                );
                             }
                         }
                     }
                 }
+                // A column declared as unique, not being a table constraint
+                "UNIQUE" => match generate_attributes(&mut table, line, restrictions) {
+                    Ok((col_name, relation)) => {
+                        info!(
+                            "Unique attribute {} processed correctly and added to table {}",
+                            col_name, table_name
+                        );
+                        relations.extend(relation);
+                    }
+                    Err(e) => {
+                        error!("An error happened while processing line : {}", e);
+                        report(
+                            position,
+                            line,
+                            format!(
+                                "The column of the table {} couldn't be understood",
+                                table_name
+                            ),
+                        );
+                    }
+                },
                 _ => warn!("The line didn't match any known relation type"),
             }
         }
     }
     info!("The table {} has been processed with success", table_name);
-    Ok(Some((table_name, dot_table, relations)))
+    Ok(Some((table_name, table, relations)))
 }
 
-/// Generate the attributes and write them into the dot_table
-fn generate_attributes(dot_table: &mut DotTable, attr: &str) -> Result<String, DoteurCoreError> {
+/// Generate the attributes and write them into the table
+///
+/// If the attribute refers to another table, the relation is returned along with the name of
+/// the attribute.
+fn generate_attributes(
+    table: &mut Table,
+    attr: &str,
+    restrictions: Option<&Restriction>,
+) -> Result<(String, Option<Relation>), DoteurCoreError> {
     let col_name: String;
     // If a PK is present in line, process attribute as pk
     if RE_PK_IN_LINE.is_match(attr) {
@@ -315,27 +472,106 @@ fn generate_attributes(dot_table: &mut DotTable, attr: &str) -> Result<String, D
         col_name = unwrap_captures_name_as_str!(captures, "col_name")
             .replace_enclosing()
             .trim_leading_trailing();
-        dot_table.add_attribute_pk(
+        table.add_column_pk(Column::new_pk(
             col_name.as_str(),
             unwrap_captures_name_as_str!(captures, "col_def"),
-        );
-        Ok(col_name)
+        ));
     // Otherwise, process as atribute
     } else {
         let captures: Captures = RE_COL_DEF.captures(attr).unwrap();
         col_name = unwrap_captures_name_as_str!(captures, "col_name")
             .replace_enclosing()
             .trim_leading_trailing();
-        dot_table.add_attribute(
+        table.add_column(Column::new(
             col_name.as_str(),
             unwrap_captures_name_as_str!(captures, "col_def"),
-        );
-        Ok(col_name)
+        ));
+    }
+    let relation: Option<Relation> = match RE_INLINE_FK_DEF.captures(attr) {
+        Some(captures) => generate_inline_relation(table, &col_name, &captures, restrictions)?,
+        None => None,
+    };
+    Ok((col_name, relation))
+}
+
+/// Returns the name of a table prefixed by its schema, if declared
+///
+/// The qualified names are resolved once every table is known, see
+/// [`Schema::resolve_table_names`].
+///
+/// # Arguments
+///
+/// * `schema` - The schema of the table as declared, if any
+/// * `table_name` - The name of the table
+fn qualify(schema: Option<&str>, table_name: &str) -> String {
+    match schema {
+        Some(schema) => format!(
+            "{}.{}",
+            schema.replace_enclosing().trim_leading_trailing(),
+            table_name
+        ),
+        None => table_name.to_string(),
+    }
+}
+
+/// Returns the relation of a foreign key declared along a column, and adds the FK nature to
+/// the column
+///
+/// If the refered key isn't declared, it is left empty until the primary key of the refered
+/// table is known.
+fn generate_inline_relation(
+    table: &mut Table,
+    col_name: &str,
+    captures: &Captures,
+    restrictions: Option<&Restriction>,
+) -> Result<Option<Relation>, DoteurCoreError> {
+    let distant_table: &str = unwrap_captures_name_as_str!(captures, "distant_table");
+    // If one of the tables doesn't match any of the restrictions, early return
+    if !restrictions
+        .is_none_or(|restriction| restriction.verify_relation(table.get_name(), distant_table))
+    {
+        info!("One of the two tables doesn't match the restrictions");
+        return Ok(None);
     }
+    let distant_key: String = captures.name("distant_key").map_or(String::new(), |m| {
+        m.as_str().replace_enclosing().trim_leading_trailing()
+    });
+    let (on_delete, on_update): (Option<ReferentialAction>, Option<ReferentialAction>) =
+        get_referential_actions(captures);
+    let distant_table: String = qualify(
+        captures.name("distant_schema").map(|m| m.as_str()),
+        distant_table,
+    );
+    table.add_fk_nature_to_column(col_name, &distant_table, distant_key.as_str())?;
+    Ok(Some(Relation::new_with_single_pair(
+        qualify(table.get_schema(), table.get_name()),
+        distant_table,
+        col_name.to_string(),
+        distant_key,
+        on_delete,
+        on_update,
+    )))
+}
+
+/// Returns the actions executed on delete and on update captured from a foreign key
+///
+/// The on update action can be declared before or after the on delete one. The actions that
+/// aren't declared are returned as None.
+fn get_referential_actions(
+    captures: &Captures,
+) -> (Option<ReferentialAction>, Option<ReferentialAction>) {
+    let on_delete: Option<ReferentialAction> = captures
+        .name("on_delete")
+        .map(|m| ReferentialAction::from_str(m.as_str()));
+    let on_update: Option<ReferentialAction> = captures
+        .name("on_update")
+        .or_else(|| captures.name("trailing_on_update"))
+        .map(|m| ReferentialAction::from_str(m.as_str()));
+    (on_delete, on_update)
 }
 
 /// Generate the attributes as primary and write them into the table
-fn generate_primary(dot_table: &mut DotTable, line: &str) -> Result<String, DoteurCoreError> {
+fn generate_primary(table: &mut Table, line: &str) -> Result<String, DoteurCoreError> {
     // Assert that the line matches regex and get the captures
     let captures: Captures = match RE_PK_DEF.captures(line) {
         Some(captures) => captures,
@@ -363,14 +599,14 @@ fn generate_primary(dot_table: &mut DotTable, line: &str) -> Result<String, Dote
         //If severeal comas are detected
         Ok(comas_vec) if !comas_vec.is_empty() => {
             for attr in col_name.split_vec(comas_vec) {
-                dot_table.add_pk_nature_to_attribute(
+                table.add_pk_nature_to_column(
                     attr.replace_enclosing().trim_leading_trailing().as_str(),
                 )?;
             }
         }
         // If no comas are detected
         _ => {
-            dot_table.add_pk_nature_to_attribute(
+            table.add_pk_nature_to_column(
                 col_name
                     .replace_enclosing()
                     .trim_leading_trailing()
@@ -409,8 +645,12 @@ fn generate_relations(
 
     let distant_table: &str = unwrap_captures_name_as_str!(captures, "distant_table");
 
-    // If one of the tables doesn't match any of the restrictions, early return
-    if !matches_optionable_restriction!(restrictive_regex, table_name, distant_table) {
+    // If one of the tables doesn't match any of the restrictions, early return, the
+    // restrictions applying to the tables regardless of their schema
+    let local_name: &str = table_name.rsplit('.').next().unwrap_or(table_name);
+    if !restrictive_regex
+        .is_none_or(|restriction| restriction.verify_relation(local_name, distant_table))
+    {
         info!("One of the two tables doesn't match the restrictions");
         return Ok(None);
     }
@@ -419,9 +659,12 @@ fn generate_relations(
     let table_key: String = unwrap_captures_name_as_str!(captures, "table_key").replace_enclosing();
     let distant_key: String =
         unwrap_captures_name_as_str!(captures, "distant_key").replace_enclosing();
-    let relation_type: &str = captures
-        .name("on_delete")
-        .map_or("RESTRICT", |m| m.as_str());
+    let (on_delete, on_update): (Option<ReferentialAction>, Option<ReferentialAction>) =
+        get_referential_actions(&captures);
+    let distant_table: String = qualify(
+        captures.name("distant_schema").map(|m| m.as_str()),
+        distant_table,
+    );
 
     // Process the input
     match detect_comas(table_key.as_str()) {
@@ -434,24 +677,17 @@ fn generate_relations(
                 {
                     let mut relation: Relation = Relation::new(
                         table_name.to_string(),
-                        distant_table.to_string(),
-                        relation_type.to_string(),
+                        distant_table.clone(),
+                        on_delete,
+                        on_update,
                     );
                     let vec_table_key: Vec<&str> = table_key.split_vec(comas_vec.clone());
                     let vec_distant_key: Vec<&str> = distant_key.split_vec(second_coma_vec);
-                    //If we have a table as input
-                    for i in 0..comas_vec.len() {
+                    // The keys are one more than the comas separating them
+                    for (table_key, distant_key) in vec_table_key.iter().zip(vec_distant_key) {
                         relation.push_pair_of_keys(
-                            vec_table_key
-                                .get(i)
-                                .unwrap()
-                                .replace_enclosing()
-                                .trim_leading_trailing(),
-                            vec_distant_key
-                                .get(i)
-                                .unwrap()
-                                .replace_enclosing()
-                                .trim_leading_trailing(),
+                            table_key.replace_enclosing().trim_leading_trailing(),
+                            distant_key.replace_enclosing().trim_leading_trailing(),
                         );
                     }
                     // If we don't
@@ -466,81 +702,779 @@ fn generate_relations(
         // Single key processing
         _ => Ok(Some(Relation::new_with_single_pair(
             table_name.to_string(),
-            distant_table.to_string(),
+            distant_table,
             table_key.replace_enclosing().trim_leading_trailing(),
             distant_key.replace_enclosing().trim_leading_trailing(),
-            relation_type.to_string(),
+            on_delete,
+            on_update,
         ))),
     }
 }
 
-/// Process the given file and return the output as a string
+/// Returns the columns, or expressions, of an index declared in parenthesis
 ///
-/// This function takes a SQL table as data and returns it as a DOT output.
+/// # Arguments
+///
+/// * `content` - The content following the opening parenthesis of the index
+/// * `dialect` - The SQL dialect of the input
+fn get_index_columns(content: &str, dialect: Dialect) -> Vec<String> {
+    split_at_comas(get_enclosed_content(content, dialect), dialect)
+        .into_iter()
+        .filter_map(|(_, part)| match part.trim_start().starts_with('(') {
+            // The expressions are kept as they are
+            true => Some(part.trim_leading_trailing()),
+            false => RE_INDEX_COLUMN.captures(part).and_then(|captures| {
+                match captures.name("call") {
+                    // As well as the function calls
+                    Some(_) => Some(part.trim_leading_trailing()),
+                    None => captures
+                        .name("col_name")
+                        .map(|m| m.as_str().replace_enclosing().trim_leading_trailing()),
+                }
+            }),
+        })
+        .collect()
+}
+
+/// Returns the index declared by a line of a table or by a clause of an alter table statement
+///
+/// None is returned if the line doesn't declare an index or a unique constraint.
+fn generate_index(line: &str, dialect: Dialect) -> Option<Index> {
+    let captures: Captures = RE_INDEX_DEF.captures(line)?;
+    Some(Index::new(
+        // The name of the constraint is used if the index isn't named
+        captures
+            .name("index_name")
+            .or_else(|| captures.name("constraint_name"))
+            .map(|m| m.as_str().replace_enclosing().trim_leading_trailing()),
+        get_index_columns(captures.name("columns")?.as_str(), dialect),
+        captures.name("unique").is_some(),
+    ))
+}
+
+/// Returns the check constraint declared in the given line, if it is one
 ///
 /// # Arguments
 ///
-/// * `data` - The SQL content as a string
+/// * `line` - The line of the table declaration or the clause of the alter table statement
+/// * `dialect` - The SQL dialect of the input
+fn generate_check(line: &str, dialect: Dialect) -> Option<Check> {
+    let captures: Captures = RE_CHECK_DEF.captures(line)?;
+    Some(Check::new(
+        captures
+            .name("constraint_name")
+            .map(|m| m.as_str().replace_enclosing().trim_leading_trailing()),
+        get_enclosed_content(captures.name("expression")?.as_str(), dialect),
+    ))
+}
+
+/// Reports a statement whose expected parts couldn't be found as a diagnostic
+///
+/// # Arguments
+///
+/// * `schema` - The schema to add the diagnostic to
+/// * `data` - The whole input, used to locate the diagnostic
+/// * `statement` - The statement that couldn't be understood
+/// * `message` - The description of the issue
+fn report_statement(schema: &mut Schema, data: &str, statement: &Statement, message: &str) {
+    warn!("{}", message);
+    schema.add_diagnostic(Diagnostic::new(data, statement.get_offset(), message));
+}
+
+/// Process a comment statement and set the comment of its table or column
+///
+/// Setting the comment to null removes it. The comments on the tables or columns that aren't
+/// part of the schema are ignored.
+///
+/// # Arguments
+///
+/// * `schema` - The schema to apply the statement to
+/// * `data` - The whole input, used to locate the diagnostics
+/// * `statement` - The comment statement
+fn process_comment(schema: &mut Schema, data: &str, statement: &Statement) {
+    let captures: Captures = match RE_COMMENT_ON.captures(statement.get_content()) {
+        Some(captures) => captures,
+        None => return,
+    };
+    let kind: &str = unwrap_captures_name_as_str!(captures, "kind", {
+        report_statement(
+            schema,
+            data,
+            statement,
+            "The kind of the comment couldn't be found",
+        );
+        return;
+    });
+    let target: Vec<String> = unwrap_captures_name_as_str!(captures, "target", {
+        report_statement(
+            schema,
+            data,
+            statement,
+            "The target of the comment couldn't be found",
+        );
+        return;
+    })
+    .split('.')
+    .map(|part| part.replace_enclosing().trim_leading_trailing())
+    .collect();
+    let comment: Option<String> = captures
+        .name("comment")
+        .map(|m| m.as_str().replace("''", "'"));
+    // The table can be prefixed by its schema, the column being the last part of the target
+    match (kind.to_uppercase().as_str(), target.as_slice()) {
+        ("TABLE", table_name) => {
+            let table_name: String = table_name.join(".");
+            if let Some(table) = schema.get_table_mut(&table_name) {
+                table.set_comment(comment);
+                debug!("Comment of the table {} set", table_name);
+            }
+        }
+        ("COLUMN", [table_name @ .., col_name]) if !table_name.is_empty() => {
+            let table_name: String = table_name.join(".");
+            if let Some(column) = schema
+                .get_table_mut(&table_name)
+                .and_then(|table| table.get_column_mut(col_name))
+            {
+                column.set_comment(comment);
+                debug!("Comment of {}.{} set", table_name, col_name);
+            }
+        }
+        _ => warn!("The target of the comment couldn't be understood"),
+    }
+}
+
+/// Process a create index statement and add the index to its table
+///
+/// # Arguments
+///
+/// * `schema` - The schema to apply the statement to
+/// * `data` - The whole input, used to locate the diagnostics
+/// * `statement` - The create index statement
+/// * `dialect` - The SQL dialect of the input
+fn process_created_index(schema: &mut Schema, data: &str, statement: &Statement, dialect: Dialect) {
+    let captures: Captures = match RE_CREATE_INDEX.captures(statement.get_content()) {
+        Some(captures) => captures,
+        None => {
+            if RE_INDEX_DECLARATION.is_match(statement.get_content()) {
+                warn!("An index declaration couldn't be understood");
+                schema.add_diagnostic(Diagnostic::new(
+                    data,
+                    statement.get_offset(),
+                    "The index declaration couldn't be understood",
+                ));
+            }
+            return;
+        }
+    };
+    let table_name: String = qualify(
+        captures.name("schema_name").map(|m| m.as_str()),
+        unwrap_captures_name_as_str!(captures, "table_name", {
+            report_statement(
+                schema,
+                data,
+                statement,
+                "The table of the index couldn't be found",
+            );
+            return;
+        }),
+    );
+    let columns: &str = unwrap_captures_name_as_str!(captures, "columns", {
+        report_statement(
+            schema,
+            data,
+            statement,
+            "The columns of the index couldn't be found",
+        );
+        return;
+    });
+    // The indexes of the tables that aren't part of the schema are ignored
+    if let Some(table) = schema.get_table_mut(&table_name) {
+        table.add_index(Index::new(
+            captures
+                .name("index_name")
+                .map(|m| m.as_str().replace_enclosing().trim_leading_trailing()),
+            get_index_columns(columns, dialect),
+            captures.name("unique").is_some(),
+        ));
+        info!("Index added to the table {}", table_name);
+    }
+}
+
+/// Process a create view statement and add the view to the schema
+///
+/// The columns of the view are the ones declared along its name, or the ones of its select
+/// list otherwise.
+///
+/// # Arguments
+///
+/// * `schema` - The schema to add the view to
+/// * `data` - The whole input, used to locate the diagnostics
+/// * `statement` - The create view statement
 /// * `restrictions` - The list of filters we want to apply on the input
-/// * `legend` - Whether we add a legend describing the types of relations at the end of the file or not.
-/// * `dark_mode` - Whether the output needs to be rendered in dark mode or not.
-pub fn process_data(
+/// * `dialect` - The SQL dialect of the input
+fn process_created_view(
+    schema: &mut Schema,
     data: &str,
+    statement: &Statement,
     restrictions: Option<&Restriction>,
-    legend: bool,
-    dark_mode: bool,
-) -> String {
-    let mut dot_file: DotFile = DotFile::new(legend, dark_mode);
+    dialect: Dialect,
+) {
+    let captures: Captures = match RE_VIEW.captures(statement.get_content()) {
+        Some(captures) => captures,
+        None => {
+            if RE_VIEW_DECLARATION.is_match(statement.get_content()) {
+                warn!("A view declaration couldn't be understood");
+                schema.add_diagnostic(Diagnostic::new(
+                    data,
+                    statement.get_offset(),
+                    "The view declaration couldn't be understood",
+                ));
+            }
+            return;
+        }
+    };
+    let view_name: &str = unwrap_captures_name_as_str!(captures, "view_name", {
+        report_statement(
+            schema,
+            data,
+            statement,
+            "The name of the view couldn't be found",
+        );
+        return;
+    });
+    let query: &str = unwrap_captures_name_as_str!(captures, "query", {
+        report_statement(
+            schema,
+            data,
+            statement,
+            "The query of the view couldn't be found",
+        );
+        return;
+    });
+    if !matches_optionable_restriction!(restrictions, view_name) {
+        info!(
+            "The view {} hasn't been added as it wasn't matching the restrictions",
+            view_name
+        );
+        return;
+    }
+    let columns: Vec<String> = match captures.name("columns") {
+        Some(columns) => split_at_comas(columns.as_str(), dialect)
+            .into_iter()
+            .map(|(_, column)| column.replace_enclosing().trim_leading_trailing())
+            .collect(),
+        None => get_select_list(query, dialect).map_or(Vec::new(), |select_list| {
+            split_at_comas(select_list, dialect)
+                .into_iter()
+                .map(|(_, item)| get_select_item_name(item, dialect))
+                .collect()
+        }),
+    };
+    schema.add_view(View::new(
+        view_name,
+        columns,
+        get_referenced_tables(query, dialect),
+    ));
+    info!("View {} added to schema", view_name);
+}
+
+/// Process the content of an alter table statement and apply it to the schema
+///
+/// The foreign keys are added as relations, while the primary keys and the default values
+/// are added to the columns of the altered table if it is part of the schema.
+///
+/// # Arguments
+///
+/// * `schema` - The schema to apply the statement to
+/// * `data` - The whole input, used to locate the diagnostics
+/// * `statement` - The alter table statement
+/// * `restrictions` - The list of filters we want to apply on the input
+/// * `dialect` - The SQL dialect of the input
+fn process_altered_table(
+    schema: &mut Schema,
+    data: &str,
+    statement: &Statement,
+    restrictions: Option<&Restriction>,
+    dialect: Dialect,
+) {
+    let captures: Captures = match RE_ALTERED_TABLE.captures(statement.get_content()) {
+        Some(captures) => captures,
+        None => return,
+    };
+    let table_name: String = qualify(
+        captures.name("schema_name").map(|m| m.as_str()),
+        unwrap_captures_name_as_str!(captures, "table_name", {
+            report_statement(
+                schema,
+                data,
+                statement,
+                "The altered table couldn't be found",
+            );
+            return;
+        }),
+    );
+    let table_name: &str = table_name.as_str();
+    let altered_content: Match = match captures.name("altered_content") {
+        Some(altered_content) => altered_content,
+        None => {
+            report_statement(
+                schema,
+                data,
+                statement,
+                "The content of the alter table statement couldn't be found",
+            );
+            return;
+        }
+    };
+    // An alter table statement can contain several clauses separated by comas
+    for (position, clause) in split_at_comas(altered_content.as_str(), dialect) {
+        let offset: usize = statement.get_offset()
+            + altered_content.start()
+            + position
+            + (clause.len() - clause.trim_start().len());
+        // The foreign keys that can't be understood are reported as well
+        let is_foreign_key: bool = RE_COL_TYPE
+            .captures(clause)
+            .and_then(|captures| captures.name("key_type"))
+            .is_some_and(|key_type| key_type.as_str().eq_ignore_ascii_case("FOREIGN"));
+        if is_foreign_key {
+            match generate_relations(table_name, clause, restrictions) {
+                Ok(Some(relation)) => {
+                    let mut undeclared_keys: Vec<&str> = Vec::new();
+                    if let Some(table) = schema.get_table_mut(table_name) {
+                        for (key, refered_key) in relation.get_pairs_of_keys() {
+                            if let Err(e) = table.add_fk_nature_to_column(
+                                key,
+                                relation.get_refered_table(),
+                                refered_key,
+                            ) {
+                                error!("An error happened while adding the FK nature of attribute {} to the table {} : {}", key, table_name, e);
+                                undeclared_keys.push(key);
+                            }
+                        }
+                    }
+                    for key in undeclared_keys {
+                        schema.add_diagnostic(Diagnostic::new(
+                            data,
+                            offset,
+                            format!(
+                                "The column {} of the foreign key isn't declared in the table {}",
+                                key, table_name
+                            )
+                            .as_str(),
+                        ));
+                    }
+                    schema.add_relation(relation);
+                    info!("New relation found and added for table : {}", table_name);
+                }
+                Ok(None) => info!(
+                    "Relation for table : {} didn't match the restrictions",
+                    table_name
+                ),
+                Err(e) => {
+                    error!("Error while processing alter table : {}", e);
+                    schema.add_diagnostic(Diagnostic::new(
+                        data,
+                        offset,
+                        format!(
+                            "The foreign key of the table {} couldn't be understood",
+                            table_name
+                        )
+                        .as_str(),
+                    ));
+                }
+            }
+        } else if RE_PK_DEF.is_match(clause) {
+            if let Some(table) = schema.get_table_mut(table_name) {
+                match generate_primary(table, clause) {
+                    Ok(m) => info!(
+                        "PK(s) {} of table {} declared on alter table processed correctly",
+                        m, table_name
+                    ),
+                    Err(e) => {
+                        error!("An error happened while processing primary key : {}", e);
+                        schema.add_diagnostic(Diagnostic::new(
+                            data,
+                            offset,
+                            format!(
+                                "The primary key of the table {} refers to columns that aren't declared",
+                                table_name
+                            )
+                            .as_str(),
+                        ));
+                    }
+                }
+            }
+        } else if let Some(captures) = RE_ALTERED_COLUMN_DEFAULT.captures(clause) {
+            let col_name: String = unwrap_captures_name_as_str!(captures, "col_name", {
+                continue;
+            })
+            .replace_enclosing();
+            let default: String = unwrap_captures_name_as_str!(captures, "default", {
+                continue;
+            })
+            .trim_leading_trailing();
+            if let Some(column) = schema
+                .get_table_mut(table_name)
+                .and_then(|table| table.get_column_mut(col_name.as_str()))
+            {
+                column.set_default(default);
+                debug!("Default value of {}.{} set", table_name, col_name);
+            }
+        } else if let Some(index) = generate_index(clause, dialect) {
+            if let Some(table) = schema.get_table_mut(table_name) {
+                table.add_index(index);
+                debug!("Index added to the table {}", table_name);
+            }
+        } else if let Some(check) = generate_check(clause, dialect) {
+            if let Some(table) = schema.get_table_mut(table_name) {
+                table.add_check(check);
+                debug!("Check constraint added to the table {}", table_name);
+            }
+        } else {
+            debug!(
+                "Alter table clause {} of table {} ignored",
+                clause.trim_leading_trailing(),
+                table_name
+            );
+        }
+    }
+}
 
-    let cleaned_content: &str = &remove_sql_comments(data);
+/// Parse the given input and return it as a schema
+///
+/// This function takes a SQL input and returns the tables and relations found in it, without
+/// rendering them. The returned schema can then be inspected or rendered with
+/// [`render_dot`].
+///
+/// # Arguments
+///
+/// * `data` - The SQL content as a string
+/// * `restrictions` - The list of filters we want to apply on the input
+///
+/// # Example
+///
+/// ```
+/// use doteur_core::parse_schema;
+/// let schema = parse_schema("
+/// CREATE TABLE author (id INT PRIMARY KEY, name VARCHAR(255) NOT NULL);
+/// CREATE TABLE book (
+///     id INT PRIMARY KEY,
+///     author_id INT,
+///     FOREIGN KEY (author_id) REFERENCES author(id) ON DELETE CASCADE
+/// );
+/// ", None);
+/// assert_eq!(schema.get_tables().len(), 2);
+/// assert_eq!(schema.get_relations()[0].get_refered_table(), "author");
+/// let name = schema.get_table("author").unwrap().get_column("name").unwrap();
+/// assert_eq!(name.get_data_type(), "VARCHAR(255)");
+/// assert!(!name.is_nullable());
+/// ```
+pub fn parse_schema(data: &str, restrictions: Option<&Restriction>) -> Schema {
+    parse_schema_with_dialect(data, restrictions, Dialect::detect(data))
+}
+
+/// Parse the given input written in the given dialect and return it as a schema
+///
+/// Unlike [`parse_schema`], the dialect of the input isn't detected.
+///
+/// # Arguments
+///
+/// * `data` - The SQL content as a string
+/// * `restrictions` - The list of filters we want to apply on the input
+/// * `dialect` - The SQL dialect of the input
+///
+/// # Example
+///
+/// ```
+/// use doteur_core::dialect::Dialect;
+/// use doteur_core::parse_schema_with_dialect;
+/// let schema = parse_schema_with_dialect("
+/// CREATE TABLE public.\"Author\" (id integer NOT NULL);
+/// ALTER TABLE ONLY public.\"Author\" ADD CONSTRAINT \"Author_pkey\" PRIMARY KEY (id);
+/// ", None, Dialect::PostgreSql);
+/// assert!(schema.get_table("Author").unwrap().get_column("id").unwrap().is_primary_key());
+/// ```
+pub fn parse_schema_with_dialect(
+    data: &str,
+    restrictions: Option<&Restriction>,
+    dialect: Dialect,
+) -> Schema {
+    let mut schema: Schema = Schema::new();
+
+    info!("Processing the input as {:?}", dialect);
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let statements: Vec<Statement> = split_statements(data, dialect, &mut diagnostics);
+    for diagnostic in diagnostics {
+        schema.add_diagnostic(diagnostic);
+    }
 
     info!("Starting to process the tables for the given input");
     // Generate content from the declared tables.
-    for table in get_tables(cleaned_content) {
-        match convert_sql_table_to_dot(table, restrictions, dark_mode) {
+    for statement in statements.iter() {
+        if !RE_TABLE_NAME.is_match(statement.get_content()) {
+            if RE_TABLE_DECLARATION.is_match(statement.get_content()) {
+                warn!("A table declaration couldn't be understood");
+                schema.add_diagnostic(Diagnostic::new(
+                    data,
+                    statement.get_offset(),
+                    "The table declaration couldn't be understood",
+                ));
+            }
+            continue;
+        }
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        match convert_sql_table(data, statement, restrictions, dialect, &mut diagnostics) {
             Ok(result) => {
-                if let Some((table_name, dot_table, relations)) = result {
-                    dot_file.add_table(dot_table);
+                if let Some((table_name, table, relations)) = result {
+                    schema.add_table(table);
                     for relation in relations {
-                        dot_file.add_relation(relation);
+                        schema.add_relation(relation);
                     }
-                    info!("Table {} added to dot file", table_name);
+                    info!("Table {} added to schema", table_name);
                 } else {
                     info!("The table hasn't been added as it wasn't matching the restrictions");
                 }
             }
-            Err(e) => error!("An error happened while processing a table : {}", e),
+            Err(e) => {
+                error!(
+                    "An error happened while processing the table at byte {} : {}",
+                    statement.get_offset(),
+                    e
+                );
+                diagnostics.push(Diagnostic::new(
+                    data,
+                    statement.get_offset(),
+                    "The table declaration couldn't be understood",
+                ));
+            }
         };
+        for diagnostic in diagnostics {
+            schema.add_diagnostic(diagnostic);
+        }
     }
 
-    // Look after the other fks, declared on alter table statements.
-    for element in RE_ALTERED_TABLE.captures_iter(cleaned_content) {
-        // Those errors shouldn't be thrown
-        let table_name: &str = unwrap_captures_name_as_str!(element, "table_name", {
-            panic!("Regex error");
-        });
-        let altered_content: &str = unwrap_captures_name_as_str!(element, "altered_content", {
-            panic!("Regex error");
+    // Look after the other keys, declared on alter table statements.
+    for statement in statements.iter() {
+        process_altered_table(&mut schema, data, statement, restrictions, dialect);
+    }
+
+    // And after the indexes and the comments declared on their own
+    for statement in statements.iter() {
+        process_created_index(&mut schema, data, statement, dialect);
+        process_comment(&mut schema, data, statement);
+    }
+
+    // And finally after the views, that can select from any table
+    for statement in statements.iter() {
+        process_created_view(&mut schema, data, statement, restrictions, dialect);
+    }
+
+    schema.resolve_table_names();
+    if let Some(restriction) =
+        restrictions.filter(|restriction| restriction.has_column_exclusions())
+    {
+        schema.retain_columns(|table_name, column_name| {
+            restriction.verify_column_name(table_name, column_name)
         });
-        match generate_relations(table_name, altered_content, restrictions) {
-            Ok(v) => {
-                if let Some(relation) = v {
-                    dot_file.add_relation(relation);
-                    info!("New relation found and added for table : {}", table_name);
-                } else {
-                    info!(
-                        "Relation for table : {} didn't match the restrictions",
-                        table_name
-                    );
-                }
-            }
-            Err(e) => error!("Error while processing alter table : {}", e),
-        };
     }
+    schema.resolve_implicit_keys();
+    schema.infer_cardinalities();
+
+    // The neighbourhood of the focused table can only be known once every relation is parsed
+    let stubs: bool = restrictions.is_some_and(|restriction| restriction.has_stubs());
+    if let Some((focus, depth)) = restrictions.and_then(|restriction| restriction.get_focus()) {
+        schema.retain_neighbourhood(focus, depth, stubs);
+    }
+    if stubs {
+        schema.collect_stubs();
+    }
+
+    info!("The data has been processed into the schema with sucess");
+    schema
+}
+
+/// Render the given schema as a DOT output
+///
+/// # Arguments
+///
+/// * `schema` - The schema to render
+/// * `legend` - Whether we add a legend describing the types of relations at the end of the file or not.
+/// * `dark_mode` - Whether the output needs to be rendered in dark mode or not.
+/// * `crows_foot` - Whether the relations are drawn with the crow's foot notation, showing their cardinality, or with arrows.
+/// * `verbosity` - How much of the definition of the columns is shown.
+///
+/// # Example
+///
+/// ```
+/// use doteur_core::{parse_schema, render_dot};
+/// use doteur_core::schema::Verbosity;
+/// let schema = parse_schema(
+///     "CREATE TABLE user (id INT PRIMARY KEY);
+///     CREATE TABLE profile (user_id INT PRIMARY KEY REFERENCES user(id));",
+///     None,
+/// );
+/// assert!(render_dot(&schema, false, false, true, Verbosity::Full).contains("arrowtail = \"teeodot\""));
+/// ```
+pub fn render_dot(
+    schema: &Schema,
+    legend: bool,
+    dark_mode: bool,
+    crows_foot: bool,
+    verbosity: Verbosity,
+) -> String {
+    DotFile::new(schema, legend, dark_mode, crows_foot, verbosity).to_string()
+}
+
+/// Render the given schema as a SVG image without requiring Graphviz
+///
+/// The tables are laid out in layers, the refering tables being placed above the tables
+/// they refer to. This function is only available with the `native_svg` feature.
+///
+/// # Arguments
+///
+/// * `schema` - The schema to render
+/// * `legend` - Whether we add a legend describing the types of relations under the tables or not.
+/// * `dark_mode` - Whether the output needs to be rendered in dark mode or not.
+/// * `crows_foot` - Whether the relations are drawn with the crow's foot notation, showing their cardinality, or with arrows.
+/// * `verbosity` - How much of the definition of the columns is shown.
+///
+/// # Example
+///
+/// ```
+/// use doteur_core::{parse_schema, render_svg};
+/// use doteur_core::schema::Verbosity;
+/// let schema = parse_schema("CREATE TABLE foo (id INT PRIMARY KEY);", None);
+/// assert!(render_svg(&schema, false, false, false, Verbosity::Type).starts_with("<svg"));
+/// ```
+#[cfg(feature = "native_svg")]
+pub fn render_svg(
+    schema: &Schema,
+    legend: bool,
+    dark_mode: bool,
+    crows_foot: bool,
+    verbosity: Verbosity,
+) -> String {
+    SvgFile::new(schema, legend, dark_mode, crows_foot, verbosity).to_string()
+}
 
-    info!("The data has been processed into the data file with sucess");
-    // Returns the content generated
-    dot_file.to_string()
+/// Render the given schema as a Mermaid entity relationship diagram
+///
+/// The output can directly be pasted in a markdown file supporting Mermaid, without
+/// requiring Graphviz.
+///
+/// # Arguments
+///
+/// * `schema` - The schema to render
+///
+/// # Example
+///
+/// ```
+/// use doteur_core::{parse_schema, render_mermaid};
+/// let schema = parse_schema("CREATE TABLE foo (id INT PRIMARY KEY);", None);
+/// assert!(render_mermaid(&schema).starts_with("erDiagram"));
+/// ```
+pub fn render_mermaid(schema: &Schema) -> String {
+    MermaidFile::new(schema).to_string()
+}
+
+/// Render the given schema as a PlantUML entity relationship diagram
+///
+/// # Arguments
+///
+/// * `schema` - The schema to render
+///
+/// # Example
+///
+/// ```
+/// use doteur_core::{parse_schema, render_plantuml};
+/// let schema = parse_schema("CREATE TABLE foo (id INT PRIMARY KEY);", None);
+/// assert!(render_plantuml(&schema).starts_with("@startuml"));
+/// ```
+pub fn render_plantuml(schema: &Schema) -> String {
+    PlantUmlFile::new(schema).to_string()
+}
+
+/// Render the given schema as DBML
+///
+/// The output can be imported in dbdiagram.io or dbdocs.
+///
+/// # Arguments
+///
+/// * `schema` - The schema to render
+///
+/// # Example
+///
+/// ```
+/// use doteur_core::{parse_schema, render_dbml};
+/// let schema = parse_schema("CREATE TABLE foo (id INT PRIMARY KEY);", None);
+/// assert!(render_dbml(&schema).starts_with("Table foo {"));
+/// ```
+pub fn render_dbml(schema: &Schema) -> String {
+    DbmlFile::new(schema).to_string()
+}
+
+/// Render the given schema as a JSON document
+///
+/// The document is meant to be read by other tools and its structure only changes along
+/// with its `version` field. It is made of :
+///
+/// * `version` - The version of the structure of the document, currently `1`
+/// * `tables` - The tables, each with its `name`, its `columns` and the names of the columns
+///   forming its `primary_key`
+/// * `tables[].columns` - The columns, each with its `name`, `type`, whether it is `nullable`,
+///   its `default` value or `null`, whether it is part of the `primary_key`, whether it is
+///   `unique` and the column it `references` as `{"table": ..., "column": ...}` or `null`
+/// * `tables[].indexes` - The indexes and unique constraints, each with its `name` or `null`,
+///   its `columns` and whether it is `unique`
+/// * `views` - The views, each with its `name`, the names of its `columns` and the tables or
+///   views it selects from as `dependencies`
+/// * `relations` - The relations, each with its `table` and `columns` refering the
+///   `referenced_table` and its `referenced_columns`, its `on_delete` and `on_update`
+///   actions written as in SQL (i.e. `CASCADE`, `SET NULL`, `RESTRICT`), its `cardinality`
+///   (i.e. `one_to_one`, `one_to_many`) and whether it is `optional`
+///
+/// # Arguments
+///
+/// * `schema` - The schema to render
+///
+/// # Example
+///
+/// ```
+/// use doteur_core::{parse_schema, render_json};
+/// let schema = parse_schema("CREATE TABLE foo (id INT PRIMARY KEY);", None);
+/// assert!(render_json(&schema).contains("\"version\": 1"));
+/// ```
+pub fn render_json(schema: &Schema) -> String {
+    let mut output: String = serde_json::to_string_pretty(&JsonFile::new(schema))
+        .expect("The serialization of a schema can't fail");
+    output.push('\n');
+    output
+}
+
+/// Process the given file and return the output as a string
+///
+/// This function takes a SQL table as data and returns it as a DOT output.
+///
+/// # Arguments
+///
+/// * `data` - The SQL content as a string
+/// * `restrictions` - The list of filters we want to apply on the input
+/// * `legend` - Whether we add a legend describing the types of relations at the end of the file or not.
+/// * `dark_mode` - Whether the output needs to be rendered in dark mode or not.
+pub fn process_data(
+    data: &str,
+    restrictions: Option<&Restriction>,
+    legend: bool,
+    dark_mode: bool,
+) -> String {
+    render_dot(
+        &parse_schema(data, restrictions),
+        legend,
+        dark_mode,
+        false,
+        Verbosity::Full,
+    )
 }
 
 #[cfg(test)]
@@ -802,14 +1736,48 @@ mod tests {
             "normal"
         );
 
-        let captures_with_on_delete_restrict_and_trailing_on_update : Captures = RE_FK_DEF.captures("FOREIGN KEY (`PersonID`) REFERENCES `Persons`(`PersonID`) ON DELETE RESTRICT ON UPDATE CASCADE").unwrap();
+        let captures_with_on_delete_restrict_and_trailing_on_update : Captures = RE_FK_DEF.captures("FOREIGN KEY (`PersonID`) REFERENCES `Persons`(`PersonID`) ON DELETE RESTRICT ON UPDATE CASCADE").unwrap();
+        assert_eq!(
+            captures_with_on_delete_restrict_and_trailing_on_update
+                .name("on_delete")
+                .unwrap()
+                .as_str(),
+            "RESTRICT",
+            "normal"
+        );
+        assert_eq!(
+            captures_with_on_delete_restrict_and_trailing_on_update
+                .name("trailing_on_update")
+                .unwrap()
+                .as_str(),
+            "CASCADE",
+            "trailing on update"
+        );
+        assert_eq!(
+            captures_with_on_delete_restrict_and_leading_on_update
+                .name("on_update")
+                .unwrap()
+                .as_str(),
+            "SET NULL",
+            "leading on update"
+        );
+
+        let captures_with_on_update_only: Captures = RE_FK_DEF
+            .captures(
+                "FOREIGN KEY (`PersonID`) REFERENCES `Persons`(`PersonID`) ON UPDATE NO ACTION",
+            )
+            .unwrap();
         assert_eq!(
-            captures_with_on_delete_restrict_and_trailing_on_update
-                .name("on_delete")
+            captures_with_on_update_only
+                .name("on_update")
                 .unwrap()
                 .as_str(),
-            "RESTRICT",
-            "normal"
+            "NO ACTION",
+            "on update only"
+        );
+        assert!(
+            captures_with_on_update_only.name("on_delete").is_none(),
+            "on update only"
         );
     }
 
@@ -925,6 +1893,23 @@ mod tests {
             "ADD FOREIGN KEY (`PersonID`) REFERENCES `artists` (`id`) ",
             "normal"
         );
+
+        let captures3 = RE_ALTERED_TABLE
+            .captures("ALTER TABLE ONLY public.\"Hello\"\n    ADD CONSTRAINT \"Hello_pkey\" PRIMARY KEY (id);")
+            .unwrap();
+        assert_eq!(
+            captures3.name("table_name").unwrap().as_str(),
+            "Hello",
+            "postgres"
+        );
+        let captures4 = RE_ALTERED_TABLE
+            .captures("ALTER TABLE only_table ADD PRIMARY KEY (id);")
+            .unwrap();
+        assert_eq!(
+            captures4.name("table_name").unwrap().as_str(),
+            "only_table",
+            "table starting with only"
+        );
     }
 
     #[test]
@@ -1215,129 +2200,1109 @@ mod tests {
     }
 
     #[test]
-    fn test_re_comments() {
-        // Test single-line comments with --
+    fn test_parse_schema() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE `orders` (
+                `id` INT NOT NULL AUTO_INCREMENT,
+                `customer_id` INT NOT NULL,
+                `note` VARCHAR(255) DEFAULT 'none',
+                `code` CHAR(8) UNIQUE,
+                PRIMARY KEY (`id`),
+                FOREIGN KEY (`customer_id`) REFERENCES `customer` (`id`) ON UPDATE CASCADE ON DELETE SET NULL
+            );
+            CREATE TABLE customer (id INT PRIMARY KEY);",
+            None,
+        );
+        assert_eq!(schema.get_tables().len(), 2, "tables");
+        let orders: &Table = schema.get_table("orders").unwrap();
+        assert_eq!(orders.get_columns().len(), 4, "columns");
+
+        let id: &Column = orders.get_column("id").unwrap();
+        assert!(id.is_primary_key(), "pk");
+        assert!(!id.is_nullable(), "pk");
+        assert_eq!(id.get_data_type(), "INT", "pk");
+
+        let customer_id: &Column = orders.get_column("customer_id").unwrap();
+        assert!(customer_id.is_foreign_key(), "fk");
+        assert_eq!(
+            customer_id.get_foreign_key().unwrap().get_table(),
+            "customer",
+            "fk"
+        );
+        assert_eq!(
+            customer_id.get_foreign_key().unwrap().get_column(),
+            "id",
+            "fk"
+        );
+
+        let note: &Column = orders.get_column("note").unwrap();
+        assert!(note.is_nullable(), "nullable");
+        assert_eq!(note.get_default(), Some("'none'"), "default");
+        assert!(orders.get_column("code").unwrap().is_unique(), "unique");
+
+        let relation: &Relation = &schema.get_relations()[0];
+        assert_eq!(relation.get_origin_table(), "orders", "relation");
+        assert_eq!(relation.get_refered_table(), "customer", "relation");
+        assert_eq!(
+            relation.get_on_delete(),
+            Some(ReferentialAction::SetNull),
+            "relation"
+        );
+        assert_eq!(
+            relation.get_on_update(),
+            Some(ReferentialAction::Cascade),
+            "relation"
+        );
+
+        let restricted: Schema = parse_schema(
+            "CREATE TABLE foo (id INT PRIMARY KEY); CREATE TABLE bar (id INT PRIMARY KEY);",
+            Some(&Restriction::new_inclusion(vec![String::from("foo")])),
+        );
+        assert_eq!(restricted.get_tables().len(), 1, "restrictions");
+        assert_eq!(restricted.get_tables()[0].get_name(), "foo", "restrictions");
+    }
+
+    #[test]
+    fn test_parse_schema_statement_boundaries() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE `note` (
+                `id` INT NOT NULL, -- the id; primary key
+                `separator` CHAR(1) DEFAULT ';',
+                `tag` VARCHAR(8) DEFAULT '#none' COMMENT 'a;b -- c',
+                `author_id` INT,
+                PRIMARY KEY (`id`),
+                FOREIGN KEY (`author_id`) REFERENCES `author` (`id`)
+            ) COMMENT='notes (a;b)';
+            CREATE TABLE author (id INT PRIMARY KEY);",
+            None,
+        );
+        assert_eq!(schema.get_tables().len(), 2, "tables");
+        let note: &Table = schema.get_table("note").unwrap();
+        assert_eq!(note.get_columns().len(), 4, "columns");
+        assert_eq!(
+            note.get_column("separator").unwrap().get_default(),
+            Some("';'"),
+            "semicolon in a string"
+        );
+        assert_eq!(
+            note.get_column("tag").unwrap().get_default(),
+            Some("'#none'"),
+            "comment markers in a string"
+        );
+        assert!(note.get_column("id").unwrap().is_primary_key(), "pk");
+        assert_eq!(schema.get_relations().len(), 1, "relation");
+
+        let schema: Schema = parse_schema_with_dialect(
+            "CREATE FUNCTION f() RETURNS void AS $$
+            BEGIN
+                CREATE TABLE ghost (id integer);
+            END;
+            $$ LANGUAGE plpgsql;
+            CREATE TABLE public.t (id integer CHECK (id <> ';'));",
+            None,
+            Dialect::PostgreSql,
+        );
+        assert_eq!(schema.get_tables().len(), 1, "function body");
+        assert_eq!(schema.get_tables()[0].get_name(), "t", "function body");
+    }
+
+    #[test]
+    fn test_parse_schema_referential_actions() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE a (id INT PRIMARY KEY);
+            CREATE TABLE b (
+                a_id INT,
+                a_other_id INT,
+                a_last_id INT,
+                a_restricted_id INT,
+                FOREIGN KEY (a_id) REFERENCES a (id) ON DELETE SET NULL ON UPDATE CASCADE,
+                FOREIGN KEY (a_other_id) REFERENCES a (id) ON UPDATE NO ACTION,
+                FOREIGN KEY (a_last_id) REFERENCES a (id) ON UPDATE SET DEFAULT ON DELETE CASCADE,
+                FOREIGN KEY (a_restricted_id) REFERENCES a (id) ON UPDATE RESTRICT
+            );",
+            None,
+        );
+        let actions: Vec<(Option<ReferentialAction>, Option<ReferentialAction>)> = schema
+            .get_relations()
+            .iter()
+            .map(|relation| (relation.get_on_delete(), relation.get_on_update()))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                (
+                    Some(ReferentialAction::SetNull),
+                    Some(ReferentialAction::Cascade)
+                ),
+                (None, Some(ReferentialAction::NoAction)),
+                (
+                    Some(ReferentialAction::Cascade),
+                    Some(ReferentialAction::SetDefault)
+                ),
+                (None, Some(ReferentialAction::Restrict))
+            ],
+            "actions in any order, undeclared ones being none"
+        );
+        let output: String = render_dot(&schema, false, false, false, Verbosity::Full);
+        assert!(
+            output.contains("<I>a_id ➡ id</I><BR/><FONT POINT-SIZE=\"10.0\">on update cascade</FONT>>, arrowhead = \"odot\", fontsize=\"12.0\", style = \"bold\""),
+            "cascading update"
+        );
+        assert!(
+            output.contains("<I>a_other_id ➡ id</I><BR/><FONT POINT-SIZE=\"10.0\">on update no action</FONT>>, arrowhead = \"normal\", fontsize=\"12.0\", ]"),
+            "other update"
+        );
+        assert!(
+            output.contains("<I>a_restricted_id ➡ id</I><BR/><FONT POINT-SIZE=\"10.0\">on update restrict</FONT>>"),
+            "declared restrict update"
+        );
+        let output: String = render_dbml(&schema);
+        assert!(
+            output.contains("Ref: b.a_other_id > a.id [update: no action]"),
+            "undeclared delete"
+        );
+        assert!(
+            output.contains("Ref: b.a_restricted_id > a.id [update: restrict]"),
+            "declared restrict update"
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_inline_references() {
+        let schema: Schema = parse_schema_with_dialect(
+            "CREATE TABLE post (
+                id INTEGER PRIMARY KEY,
+                user_id INTEGER NOT NULL REFERENCES \"user\"(id) ON DELETE CASCADE,
+                category_id INTEGER REFERENCES category ON UPDATE CASCADE ON DELETE SET NULL
+            );
+            CREATE TABLE \"user\" (id INTEGER PRIMARY KEY);
+            CREATE TABLE category (code TEXT PRIMARY KEY);
+            CREATE TABLE profile (user_id INTEGER PRIMARY KEY REFERENCES \"user\" (id));",
+            None,
+            Dialect::Sqlite,
+        );
+        let relations: Vec<String> = schema
+            .get_relations()
+            .iter()
+            .map(|relation| {
+                format!(
+                    "{}.{} -> {}.{} ({}, {})",
+                    relation.get_origin_table(),
+                    relation.get_pairs_of_keys()[0].0,
+                    relation.get_refered_table(),
+                    relation.get_pairs_of_keys()[0].1,
+                    relation
+                        .get_on_delete()
+                        .map_or("-", |action| action.as_sql()),
+                    relation
+                        .get_on_update()
+                        .map_or("-", |action| action.as_sql())
+                )
+            })
+            .collect();
+        assert_eq!(
+            relations,
+            vec![
+                "post.user_id -> user.id (CASCADE, -)",
+                "post.category_id -> category.code (SET NULL, CASCADE)",
+                "profile.user_id -> user.id (-, -)"
+            ],
+            "relations"
+        );
+
+        let post: &Table = schema.get_table("post").unwrap();
+        let user_id: &Column = post.get_column("user_id").unwrap();
+        assert!(user_id.is_foreign_key(), "fk nature");
+        assert_eq!(user_id.get_data_type(), "INTEGER", "type");
+        assert!(!user_id.is_nullable(), "not null");
+        assert_eq!(
+            post.get_column("category_id")
+                .unwrap()
+                .get_foreign_key()
+                .unwrap()
+                .get_column(),
+            "code",
+            "implicit refered key"
+        );
+        let profile_id: &Column = schema
+            .get_table("profile")
+            .unwrap()
+            .get_column("user_id")
+            .unwrap();
+        assert!(
+            profile_id.is_primary_key() && profile_id.is_foreign_key(),
+            "primary and foreign key"
+        );
+
+        let restricted: Schema = parse_schema(
+            "CREATE TABLE a (id INT PRIMARY KEY);
+            CREATE TABLE b (a_id INT REFERENCES a(id));",
+            Some(&Restriction::new_exclusion(vec![String::from("a")])),
+        );
+        assert!(restricted.get_relations().is_empty(), "restrictions");
+        assert!(
+            !restricted
+                .get_table("b")
+                .unwrap()
+                .get_column("a_id")
+                .unwrap()
+                .is_foreign_key(),
+            "restrictions"
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_indexes() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE `user` (
+                `id` INT PRIMARY KEY,
+                `email` VARCHAR(255) UNIQUE NOT NULL,
+                `first_name` VARCHAR(255),
+                `last_name` VARCHAR(255),
+                `login` VARCHAR(255),
+                `team_id` INT,
+                UNIQUE KEY `uk_login` (`login`),
+                KEY `idx_name` (`last_name`(10), `first_name` DESC),
+                CONSTRAINT `uk_team` UNIQUE (`team_id`, `last_name`),
+                INDEX (`team_id`)
+            );
+            ALTER TABLE `user` ADD CONSTRAINT `uk_names` UNIQUE (`first_name`, `last_name`);
+            CREATE UNIQUE INDEX user_lower_email ON public.user USING btree (lower(email));
+            CREATE INDEX unknown_idx ON unknown (id);",
+            None,
+        );
+        let user: &Table = schema.get_table("user").unwrap();
+        assert_eq!(user.get_columns().len(), 6, "unique column kept");
+        assert!(
+            user.get_column("email").unwrap().is_unique(),
+            "unique column"
+        );
+        assert!(
+            user.get_column("login").unwrap().is_unique(),
+            "unique constraint on a single column"
+        );
+        assert!(
+            !user.get_column("team_id").unwrap().is_unique(),
+            "unique constraint on several columns"
+        );
+        let indexes: Vec<String> = user
+            .get_indexes()
+            .iter()
+            .map(|index| {
+                format!(
+                    "{} {} ({})",
+                    index.get_name().unwrap_or("?"),
+                    index.is_unique(),
+                    index.get_columns().join(", ")
+                )
+            })
+            .collect();
+        assert_eq!(
+            indexes,
+            vec![
+                "uk_login true (login)",
+                "idx_name false (last_name, first_name)",
+                "uk_team true (team_id, last_name)",
+                "? false (team_id)",
+                "uk_names true (first_name, last_name)",
+                "user_lower_email true (lower(email))"
+            ],
+            "indexes"
+        );
+        assert!(schema.get_diagnostics().is_empty(), "no diagnostic");
+        assert!(
+            render_dot(&schema, false, false, false, Verbosity::Full).contains("<HR/>"),
+            "indexes rendered"
+        );
+
+        let schema: Schema = parse_schema(
+            "CREATE TABLE t (a INT, b INT); CREATE INDEX \"a&b\" ON t ((a < b));",
+            None,
+        );
+        let output: String = render_dot(&schema, false, false, false, Verbosity::Full);
+        assert!(output.contains("<I>a&amp;b</I>"), "escaped index name");
         assert!(
-            RE_COMMENTS.is_match("-- This is a single-line comment"),
-            "single-line comment with --"
+            output.contains("INDEX ((a &lt; b))"),
+            "escaped index expression"
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_checks() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE product (
+                id INT PRIMARY KEY,
+                price DECIMAL(10, 2) NOT NULL CHECK (price > 0),
+                discount DECIMAL(10, 2),
+                CHECK (discount < price),
+                CONSTRAINT `positive_discount` CHECK (discount >= 0)
+            );
+            ALTER TABLE product ADD CONSTRAINT small_id CHECK (id < 1000);",
+            None,
+        );
+        let product: &Table = schema.get_table("product").unwrap();
+        assert_eq!(
+            product.get_columns().len(),
+            3,
+            "column with a check constraint kept"
+        );
+        assert_eq!(
+            product.get_column("price").unwrap().get_check(),
+            Some("price > 0"),
+            "column check"
+        );
+        let checks: Vec<String> = product
+            .get_checks()
+            .iter()
+            .map(|check| {
+                format!(
+                    "{} ({})",
+                    check.get_name().unwrap_or("?"),
+                    check.get_expression()
+                )
+            })
+            .collect();
+        assert_eq!(
+            checks,
+            vec![
+                "? (discount < price)",
+                "positive_discount (discount >= 0)",
+                "small_id (id < 1000)"
+            ],
+            "table checks"
         );
+        assert!(schema.get_diagnostics().is_empty(), "no diagnostic");
+
+        let output: String = render_dot(&schema, false, false, false, Verbosity::Full);
         assert!(
-            RE_COMMENTS.is_match("SELECT * FROM users; -- This is another comment"),
-            "single-line comment with -- at the end of a line"
+            output.contains("CHECK (discount &lt; price)"),
+            "checks rendered and escaped"
+        );
+        let output: String = render_dot(&schema, false, false, false, Verbosity::Type);
+        assert!(!output.contains("CHECK"), "checks hidden");
+        assert!(!output.contains("NOT NULL"), "nullability hidden");
+        let output: String = render_dot(&schema, false, false, false, Verbosity::Nullability);
+        assert!(output.contains("DECIMAL(10, 2) NOT NULL"), "nullability");
+    }
+
+    #[test]
+    fn test_parse_schema_comments() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE `user` (
+                `id` INT NOT NULL AUTO_INCREMENT COMMENT 'The \"identifier\"',
+                `name` VARCHAR(255) COMMENT 'Full name',
+                PRIMARY KEY (`id`)
+            ) ENGINE=InnoDB DEFAULT CHARSET=utf8 COMMENT='People using the app';
+            CREATE TABLE post (id INT, title TEXT);
+            COMMENT ON TABLE public.post IS 'What the users write';
+            COMMENT ON COLUMN public.post.title IS 'It''s shown first';
+            COMMENT ON COLUMN user.name IS NULL;
+            COMMENT ON TABLE unknown IS 'Ignored';",
+            None,
+        );
+        let user: &Table = schema.get_table("user").unwrap();
+        assert_eq!(
+            user.get_comment(),
+            Some("People using the app"),
+            "table option"
+        );
+        assert_eq!(
+            user.get_column("id").unwrap().get_comment(),
+            Some("The \"identifier\""),
+            "column comment"
+        );
+        assert_eq!(
+            user.get_column("name").unwrap().get_comment(),
+            None,
+            "comment removed"
+        );
+        let post: &Table = schema.get_table("post").unwrap();
+        assert_eq!(
+            post.get_comment(),
+            Some("What the users write"),
+            "comment on table"
+        );
+        assert_eq!(
+            post.get_column("title").unwrap().get_comment(),
+            Some("It's shown first"),
+            "comment on column"
         );
+        assert!(schema.get_diagnostics().is_empty(), "no diagnostic");
 
-        // Test single-line comments with #
+        let output: String = render_dot(&schema, false, false, false, Verbosity::Full);
         assert!(
-            RE_COMMENTS.is_match("# This is a comment with hash"),
-            "single-line comment with #"
+            output.contains("</TABLE> >, tooltip=\"People using the app\"]"),
+            "table tooltip"
         );
         assert!(
-            RE_COMMENTS.is_match("SELECT * FROM products; # End of query"),
-            "single-line comment with # at the end of a line"
+            output.contains(" HREF=\"#\" TOOLTIP=\"The &quot;identifier&quot;\">"),
+            "column tooltip"
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_schemas() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE billing.invoice (id INT PRIMARY KEY, order_id INT);
+            CREATE TABLE billing.payment (id INT PRIMARY KEY, invoice_id INT REFERENCES invoice(id));
+            CREATE TABLE \"sales\".\"invoice\" (id INT PRIMARY KEY);
+            CREATE TABLE sales.order (id INT PRIMARY KEY, invoice_id INT);
+            CREATE TABLE currency (code CHAR(3) PRIMARY KEY);
+            ALTER TABLE ONLY sales.order ADD FOREIGN KEY (invoice_id) REFERENCES billing.invoice(id);
+            ALTER TABLE billing.invoice ADD FOREIGN KEY (order_id) REFERENCES sales.order(id);
+            COMMENT ON TABLE sales.invoice IS 'Quotes sent to the customers';",
+            None,
+        );
+        let names: Vec<&str> = schema
+            .get_tables()
+            .iter()
+            .map(|table| table.get_name())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "billing.invoice",
+                "billing.payment",
+                "sales.invoice",
+                "sales.order",
+                "currency"
+            ],
+            "qualified names"
+        );
+        assert_eq!(
+            schema.get_schema_names(),
+            vec!["billing", "sales"],
+            "schema names"
+        );
+        let relations: Vec<String> = schema
+            .get_relations()
+            .iter()
+            .map(|relation| {
+                format!(
+                    "{} -> {}",
+                    relation.get_origin_table(),
+                    relation.get_refered_table()
+                )
+            })
+            .collect();
+        assert_eq!(
+            relations,
+            vec![
+                "billing.payment -> billing.invoice",
+                "sales.order -> billing.invoice",
+                "billing.invoice -> sales.order"
+            ],
+            "relations resolved"
+        );
+        assert_eq!(
+            schema
+                .get_table("billing.payment")
+                .and_then(|table| table.get_column("invoice_id"))
+                .and_then(|column| column.get_foreign_key())
+                .map(|reference| reference.get_table()),
+            Some("billing.invoice"),
+            "column reference resolved"
+        );
+        assert_eq!(
+            schema
+                .get_table("billing.invoice")
+                .unwrap()
+                .get_primary_key()[0]
+                .get_name(),
+            "id",
+            "implicit key resolved"
+        );
+        assert_eq!(
+            schema.get_table("sales.invoice").unwrap().get_comment(),
+            Some("Quotes sent to the customers"),
+            "comment on the right table"
         );
 
-        // Test multi-line comments with /* */
+        let output: String = render_dot(&schema, false, false, false, Verbosity::Full);
         assert!(
-            RE_COMMENTS.is_match("/* This is a multi-line comment */"),
-            "multi-line comment"
+            output.contains("subgraph \"cluster_billing\" {\n        label = \"billing\";"),
+            "cluster"
         );
         assert!(
-            RE_COMMENTS.is_match("SELECT * FROM orders; /* Comment within SQL */"),
-            "multi-line comment inside SQL query"
+            output.contains("\t\"sales.order\" -> \"billing.invoice\" [label=<<I>invoice_id"),
+            "cross schema relation"
+        );
+
+        let schema: Schema = parse_schema(
+            "CREATE TABLE public.author (id INT PRIMARY KEY);
+            CREATE TABLE public.book (id INT PRIMARY KEY, author_id INT REFERENCES public.author(id));",
+            None,
+        );
+        assert_eq!(
+            schema.get_relations()[0].get_refered_table(),
+            "author",
+            "single schema kept unqualified"
         );
         assert!(
-            RE_COMMENTS.is_match("/* This is a\nmulti-line comment */"),
-            "multi-line comment spanning multiple lines"
+            !render_dot(&schema, false, false, false, Verbosity::Full).contains("subgraph"),
+            "no cluster for a single schema"
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_cardinalities() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE user (id INT PRIMARY KEY, email VARCHAR(255));
+            CREATE TABLE post (id INT PRIMARY KEY, user_id INT NOT NULL REFERENCES user(id));
+            CREATE TABLE profile (user_id INT PRIMARY KEY REFERENCES user(id));
+            CREATE TABLE avatar (id INT PRIMARY KEY, user_id INT UNIQUE REFERENCES user(id));
+            CREATE TABLE badge (
+                user_id INT NOT NULL,
+                post_id INT,
+                name VARCHAR(255),
+                UNIQUE KEY (user_id, name),
+                FOREIGN KEY (user_id) REFERENCES user(id),
+                FOREIGN KEY (post_id) REFERENCES post(id)
+            );
+            CREATE TABLE session (id INT PRIMARY KEY, user_id INT);
+            ALTER TABLE session ADD CONSTRAINT session_user UNIQUE (user_id);
+            ALTER TABLE session ADD FOREIGN KEY (user_id) REFERENCES user(id);",
+            None,
+        );
+        let relations: Vec<String> = schema
+            .get_relations()
+            .iter()
+            .map(|relation| {
+                format!(
+                    "{} {} {}",
+                    relation.get_origin_table(),
+                    relation.get_cardinality().as_str(),
+                    relation.is_optional()
+                )
+            })
+            .collect();
+        assert_eq!(
+            relations,
+            vec![
+                "post one_to_many false",
+                "profile one_to_one false",
+                "avatar one_to_one true",
+                "badge one_to_many false",
+                "badge one_to_many true",
+                "session one_to_one true"
+            ],
+            "cardinalities"
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_focus() {
+        let data: &str = "CREATE TABLE customer (id INT PRIMARY KEY, country_id INT REFERENCES country(id));
+            CREATE TABLE country (id INT PRIMARY KEY);
+            CREATE TABLE orders (id INT PRIMARY KEY, customer_id INT);
+            CREATE TABLE order_line (order_id INT REFERENCES orders(id), product_id INT REFERENCES product(id));
+            CREATE TABLE product (id INT PRIMARY KEY, supplier_id INT REFERENCES supplier(id));
+            CREATE TABLE supplier (id INT PRIMARY KEY);
+            CREATE TABLE audit (id INT PRIMARY KEY);
+            ALTER TABLE orders ADD FOREIGN KEY (customer_id) REFERENCES customer(id);
+            CREATE VIEW order_summary AS SELECT * FROM orders JOIN order_line ON id = order_id;
+            CREATE VIEW catalog AS SELECT * FROM product;";
+        let table_names = |schema: &Schema| -> Vec<String> {
+            schema
+                .get_tables()
+                .iter()
+                .map(|table| table.get_name().to_string())
+                .collect()
+        };
+
+        let focus: Restriction = Restriction::new_inclusion(vec![]).with_focus("orders", 1);
+        let schema: Schema = parse_schema(data, Some(&focus));
+        assert_eq!(
+            table_names(&schema),
+            vec!["customer", "orders", "order_line"],
+            "both directions, altered keys included"
+        );
+        assert_eq!(
+            schema.get_relations().len(),
+            2,
+            "relations between kept tables"
+        );
+        assert_eq!(
+            schema
+                .get_views()
+                .iter()
+                .map(|view| view.get_name())
+                .collect::<Vec<&str>>(),
+            vec!["order_summary"],
+            "views of kept tables"
+        );
+
+        let focus: Restriction = Restriction::new_inclusion(vec![]).with_focus("orders", 2);
+        assert_eq!(
+            table_names(&parse_schema(data, Some(&focus))),
+            vec!["customer", "country", "orders", "order_line", "product"],
+            "two hops"
+        );
+
+        let focus: Restriction = Restriction::new_inclusion(vec![]).with_focus("orders", 0);
+        assert_eq!(
+            table_names(&parse_schema(data, Some(&focus))),
+            vec!["orders"],
+            "no hop"
+        );
+
+        let focus: Restriction =
+            Restriction::new_exclusion(vec![String::from("customer")]).with_focus("orders", 2);
+        assert_eq!(
+            table_names(&parse_schema(data, Some(&focus))),
+            vec!["orders", "order_line", "product"],
+            "excluded tables break the paths"
         );
 
-        // Test comments with special characters or mixed spacing
+        let focus: Restriction = Restriction::new_inclusion(vec![]).with_focus("unknown", 2);
         assert!(
-            RE_COMMENTS.is_match("  -- Comment with leading spaces"),
-            "single-line comment with leading spaces"
+            parse_schema(data, Some(&focus)).get_tables().is_empty(),
+            "unknown focus"
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_stubs() {
+        let data: &str = "CREATE TABLE customer (id INT PRIMARY KEY, country_id INT REFERENCES country(id));
+            CREATE TABLE country (id INT PRIMARY KEY);
+            CREATE TABLE orders (id INT PRIMARY KEY, customer_id INT REFERENCES customer(id), seller_id INT);
+            CREATE TABLE seller (id INT PRIMARY KEY);
+            ALTER TABLE orders ADD FOREIGN KEY (seller_id) REFERENCES seller(id);
+            ALTER TABLE customer ADD FOREIGN KEY (id) REFERENCES orders(id);";
+        let relations = |schema: &Schema| -> Vec<String> {
+            schema
+                .get_relations()
+                .iter()
+                .map(|relation| {
+                    format!(
+                        "{} -> {}",
+                        relation.get_origin_table(),
+                        relation.get_refered_table()
+                    )
+                })
+                .collect()
+        };
+
+        let exclusion: Restriction =
+            Restriction::new_exclusion(vec![String::from("customer"), String::from("seller")]);
+        let schema: Schema = parse_schema(data, Some(&exclusion));
+        assert!(relations(&schema).is_empty(), "relations dropped");
+        assert!(schema.get_stubs().is_empty(), "no stub");
+
+        let schema: Schema = parse_schema(data, Some(&exclusion.with_stubs(true)));
+        assert_eq!(
+            relations(&schema),
+            vec!["orders -> customer", "orders -> seller"],
+            "relations toward the excluded tables kept"
         );
+        assert_eq!(schema.get_stubs(), ["customer", "seller"], "stubs");
+        assert_eq!(
+            schema
+                .get_table("orders")
+                .and_then(|table| table.get_column("customer_id"))
+                .and_then(|column| column.get_foreign_key())
+                .map(|reference| reference.get_table()),
+            Some("customer"),
+            "foreign key kept"
+        );
+        let output: String = render_dot(&schema, false, false, false, Verbosity::Full);
         assert!(
-            RE_COMMENTS.is_match("    # Another comment with spaces before hash"),
-            "single-line comment with spaces before #"
+            output.contains("\"customer\" [label=<") && output.contains("STYLE=\"dashed\""),
+            "stub node"
         );
         assert!(
-            RE_COMMENTS.is_match("  /* A multi-line\n  comment with indentation */"),
-            "multi-line comment with indentation"
+            output.contains("\t\"orders\" -> \"customer\""),
+            "relation toward the stub"
+        );
+
+        let focus: Restriction = Restriction::new_inclusion(vec![])
+            .with_focus("country", 1)
+            .with_stubs(true);
+        let schema: Schema = parse_schema(data, Some(&focus));
+        assert_eq!(
+            relations(&schema),
+            vec!["customer -> country", "customer -> orders"],
+            "relations toward the tables out of focus kept"
+        );
+        assert_eq!(schema.get_stubs(), ["orders"], "tables out of focus");
+    }
+
+    #[test]
+    fn test_parse_schema_combined_restrictions() {
+        let data: &str = "CREATE TABLE user (id INT PRIMARY KEY, name TEXT, created_at DATETIME, created_by INT REFERENCES user(id));
+            CREATE TABLE user_role (user_id INT REFERENCES user(id), role_id INT REFERENCES role(id), created_at DATETIME);
+            CREATE TABLE user_log (user_id INT REFERENCES user(id));
+            CREATE TABLE role (id INT PRIMARY KEY, created_at DATETIME);";
+        let restriction: Restriction = Restriction::new_inclusion(vec![String::from("user*")])
+            .with_exclusion(vec![String::from("re:_log$")])
+            .with_column_exclusion(vec![String::from("*.created_*")]);
+        let schema: Schema = parse_schema(data, Some(&restriction));
+        assert_eq!(
+            schema
+                .get_tables()
+                .iter()
+                .map(|table| table.get_name())
+                .collect::<Vec<&str>>(),
+            vec!["user", "user_role"],
+            "included then excluded"
+        );
+        assert_eq!(
+            schema
+                .get_table("user")
+                .unwrap()
+                .get_columns()
+                .iter()
+                .map(|column| column.get_name())
+                .collect::<Vec<&str>>(),
+            vec!["id", "name"],
+            "excluded columns"
+        );
+        assert_eq!(
+            schema.get_table("user_role").unwrap().get_columns().len(),
+            2,
+            "excluded columns of every table"
+        );
+        assert_eq!(
+            schema
+                .get_relations()
+                .iter()
+                .map(|relation| relation.get_origin_table())
+                .collect::<Vec<&str>>(),
+            vec!["user_role"],
+            "relations of the excluded columns removed"
         );
+    }
 
-        // Ensure non-matching text does not match
+    #[test]
+    fn test_parse_schema_views() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE dept_emp (emp_no INT, from_date DATE, to_date DATE);
+            CREATE OR REPLACE ALGORITHM=UNDEFINED DEFINER=`root`@`localhost` SQL SECURITY DEFINER VIEW `dept_emp_latest_date` AS
+                SELECT emp_no, MAX(from_date) AS from_date, MAX(to_date) AS to_date
+                FROM dept_emp
+                GROUP BY emp_no;
+            CREATE VIEW current_dept_emp (emp, since) AS
+                SELECT l.emp_no, l.from_date
+                FROM dept_emp d
+                    INNER JOIN dept_emp_latest_date l ON d.emp_no = l.emp_no;
+            CREATE VIEW broken;",
+            None,
+        );
+        let views: Vec<String> = schema
+            .get_views()
+            .iter()
+            .map(|view| {
+                format!(
+                    "{} ({}) -> {}",
+                    view.get_name(),
+                    view.get_columns().join(", "),
+                    view.get_dependencies().join(", ")
+                )
+            })
+            .collect();
+        assert_eq!(
+            views,
+            vec![
+                "dept_emp_latest_date (emp_no, from_date, to_date) -> dept_emp",
+                "current_dept_emp (emp, since) -> dept_emp, dept_emp_latest_date"
+            ],
+            "views"
+        );
+        assert_eq!(
+            schema.get_diagnostics().len(),
+            1,
+            "malformed view declaration"
+        );
+        let output: String = render_dot(&schema, false, false, false, Verbosity::Full);
+        assert!(output.contains("BGCOLOR=\"darkcyan\""), "view header");
         assert!(
-            !RE_COMMENTS.is_match("SELECT * FROM users;"),
-            "text without comments"
+            output.contains(
+                "\"current_dept_emp\" -> \"dept_emp_latest_date\" [style = \"dashed\", arrowhead = \"empty\"]"
+            ),
+            "view dependency"
+        );
+
+        let restricted: Schema = parse_schema(
+            "CREATE TABLE a (id INT);
+            CREATE VIEW b AS SELECT id FROM a;",
+            Some(&Restriction::new_exclusion(vec![String::from("b")])),
+        );
+        assert!(restricted.get_views().is_empty(), "restrictions");
+    }
+
+    #[test]
+    fn test_parse_schema_diagnostics() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE author (id INT PRIMARY KEY);
+CREATE TABLE book (
+    id INT,
+    PRIMARY KEY (uuid),
+    FOREIGN KEY (author_id) REFERENCES author (id)
+);
+CREATE TABLE copy AS SELECT * FROM book;
+ALTER TABLE book ADD FOREIGN KEY (id) REFERENCES;",
+            None,
+        );
+        assert_eq!(schema.get_tables().len(), 2, "tables");
+        let diagnostics: Vec<(usize, usize)> = schema
+            .get_diagnostics()
+            .iter()
+            .map(|diagnostic| (diagnostic.get_line(), diagnostic.get_column()))
+            .collect();
+        assert_eq!(
+            diagnostics,
+            vec![(4, 5), (5, 5), (7, 1), (8, 18)],
+            "diagnostics"
+        );
+        assert_eq!(
+            schema.get_diagnostics()[1].get_snippet(),
+            "    FOREIGN KEY (author_id) REFERENCES author (id)",
+            "snippet"
         );
         assert!(
-            !RE_COMMENTS.is_match("CREATE TABLE products (id INT, name VARCHAR);"),
-            "SQL definition without comments"
+            parse_schema("CREATE TABLE author (id INT PRIMARY KEY);", None)
+                .get_diagnostics()
+                .is_empty(),
+            "no diagnostic"
+        );
+    }
+
+    #[test]
+    fn test_postgres_dump_matches_mysql_dump() {
+        let mysql: Schema =
+            parse_schema(include_str!("../../samples/sample_mysql_file3.sql"), None);
+        let postgres: Schema = parse_schema(
+            include_str!("../../samples/sample_postgres_file1.sql"),
+            None,
+        );
+
+        // Describe the schema in a comparable way, the order of declaration being irrelevant
+        let describe_tables = |schema: &Schema| {
+            let mut tables: Vec<String> = schema
+                .get_tables()
+                .iter()
+                .flat_map(|table| {
+                    table.get_columns().iter().map(move |c| {
+                        format!(
+                            "{}.{} pk:{} fk:{}",
+                            table.get_name(),
+                            c.get_name(),
+                            c.is_primary_key(),
+                            c.is_foreign_key()
+                        )
+                    })
+                })
+                .collect();
+            tables.sort();
+            tables
+        };
+        let describe_relations = |schema: &Schema| {
+            let mut relations: Vec<String> = schema
+                .get_relations()
+                .iter()
+                .map(|r| {
+                    format!(
+                        "{} -> {} {:?} {:?}",
+                        r.get_origin_table(),
+                        r.get_refered_table(),
+                        r.get_pairs_of_keys(),
+                        r.get_on_delete()
+                    )
+                })
+                .collect();
+            relations.sort();
+            relations
+        };
+
+        assert_eq!(postgres.get_tables().len(), 6, "tables");
+        assert_eq!(
+            describe_tables(&mysql),
+            describe_tables(&postgres),
+            "tables"
+        );
+        assert_eq!(postgres.get_relations().len(), 6, "relations");
+        assert_eq!(
+            describe_relations(&mysql),
+            describe_relations(&postgres),
+            "relations"
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_mysql_serial() {
+        let data: &str = "CREATE TABLE a (id SERIAL, c VARCHAR(20) DEFAULT 'it\\'s; fine'); # comment;\nCREATE TABLE b (id SERIAL);";
+        assert_eq!(Dialect::detect(data), Dialect::MySql, "dialect");
+        let schema: Schema = parse_schema(data, None);
+        assert_eq!(schema.get_tables().len(), 2, "tables");
+        assert!(schema.get_table("b").is_some(), "table after the escape");
+
+        let schema: Schema = parse_schema_with_dialect(
+            "CREATE TABLE a (id SERIAL);\nCREATE TABLE b (id INT DEFAULT 'x'::text);",
+            None,
+            Dialect::MySql,
+        );
+        assert_eq!(schema.get_tables().len(), 2, "given dialect");
+    }
+
+    #[test]
+    fn test_parse_schema_mysql_cast_in_comment() {
+        let data: &str = "-- MySQL dump 10.13\n\
+            /* Casts such as Foo::Bar are only written in comments */\n\
+            CREATE TABLE `a` (\n\
+              `id` int NOT NULL COMMENT 'Foo::Bar',\n\
+              `name` varchar(20) DEFAULT 'it\\'s',\n\
+              PRIMARY KEY (`id`)\n\
+            ) ENGINE=InnoDB;\n\
+            CREATE TABLE `b` (\n\
+              `id` int NOT NULL,\n\
+              `a_id` int NOT NULL,\n\
+              CONSTRAINT `fk_b_a` FOREIGN KEY (`a_id`) REFERENCES `a` (`id`)\n\
+            ) ENGINE=InnoDB;";
+        assert_eq!(Dialect::detect(data), Dialect::MySql, "dialect");
+        let schema: Schema = parse_schema(data, None);
+        assert_eq!(schema.get_tables().len(), 2, "tables");
+        assert_eq!(schema.get_relations().len(), 1, "foreign key");
+        assert!(schema.get_diagnostics().is_empty(), "diagnostics");
+    }
+
+    #[test]
+    fn test_postgres_dump_casts() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE public.invoice (
+    id integer NOT NULL,
+    reference character varying(32) DEFAULT 'draft'::character varying NOT NULL,
+    note character varying DEFAULT NULL::character varying,
+    issued_at timestamp without time zone DEFAULT '2024-01-01 00:00:00'::timestamp without time zone NOT NULL,
+    amount numeric(10,2) DEFAULT (0)::numeric(10,2) NOT NULL,
+    rate double precision DEFAULT '1'::double precision
+);",
+            None,
+        );
+        let invoice: &Table = schema.get_table("invoice").unwrap();
+        for (column, default, nullable) in [
+            ("reference", "'draft'::character varying", false),
+            ("note", "NULL::character varying", true),
+            (
+                "issued_at",
+                "'2024-01-01 00:00:00'::timestamp without time zone",
+                false,
+            ),
+            ("amount", "(0)::numeric(10,2)", false),
+            ("rate", "'1'::double precision", true),
+        ] {
+            let column: &Column = invoice.get_column(column).unwrap();
+            assert_eq!(column.get_default(), Some(default), "{}", default);
+            assert_eq!(column.is_nullable(), nullable, "{}", default);
+        }
+    }
+
+    #[test]
+    fn test_postgres_dump() {
+        let data: &str = include_str!("../../samples/sample_postgres_file2.sql");
+        assert_eq!(Dialect::detect(data), Dialect::PostgreSql, "dialect");
+        let schema: Schema = parse_schema(data, None);
+
+        let mut tables: Vec<&str> = schema.get_tables().iter().map(|t| t.get_name()).collect();
+        tables.sort_unstable();
+        assert_eq!(
+            tables,
+            vec![
+                "UserAccount",
+                "address",
+                "digital_product",
+                "order_line",
+                "product",
+                "shop_order"
+            ],
+            "tables"
+        );
+
+        let user: &Table = schema.get_table("UserAccount").unwrap();
+        let user_id: &Column = user.get_column("userId").unwrap();
+        assert!(user_id.is_primary_key(), "quoted pk");
+        assert_eq!(
+            user_id.get_default(),
+            Some("nextval('public.\"UserAccount_userId_seq\"'::regclass)"),
+            "serial default"
+        );
+        assert_eq!(
+            user.get_column("createdAt").unwrap().get_data_type(),
+            "timestamp with time zone",
+            "type"
         );
 
-        // Check the capturing groups to ensure proper comment capture
         assert_eq!(
-            RE_COMMENTS
-                .captures("-- This is a comment")
+            schema
+                .get_table("address")
                 .unwrap()
-                .get(0)
+                .get_column("country")
                 .unwrap()
-                .as_str(),
-            "-- This is a comment",
-            "capture single-line comment with --"
+                .get_default(),
+            Some("'FR'::bpchar"),
+            "cast"
         );
         assert_eq!(
-            RE_COMMENTS
-                .captures("# This is a hash comment")
+            schema
+                .get_table("product")
                 .unwrap()
-                .get(0)
+                .get_column("tags")
                 .unwrap()
-                .as_str(),
-            "# This is a hash comment",
-            "capture single-line comment with #"
+                .get_default(),
+            Some("'{}'::text[]"),
+            "array cast"
         );
+
+        let digital_product: &Table = schema.get_table("digital_product").unwrap();
+        assert_eq!(digital_product.get_columns().len(), 1, "inherits");
+
+        let order_line: &Table = schema.get_table("order_line").unwrap();
+        assert_eq!(order_line.get_primary_key().len(), 2, "composite pk");
+        assert_eq!(order_line.get_columns().len(), 3, "check ignored");
+        assert!(order_line.get_column("orderId").unwrap().is_foreign_key());
+
         assert_eq!(
-            RE_COMMENTS
-                .captures("/* This is a multi-line comment */")
+            schema
+                .get_table("shop_order")
                 .unwrap()
-                .get(0)
+                .get_column("status")
                 .unwrap()
-                .as_str(),
-            "/* This is a multi-line comment */",
-            "capture multi-line comment"
+                .get_data_type(),
+            "public.\"OrderStatus\"",
+            "enum"
         );
         assert_eq!(
-            RE_COMMENTS
-                .captures("SELECT * FROM orders; /* Comment at the end */")
+            schema
+                .get_table("shop_order")
                 .unwrap()
-                .get(0)
+                .get_column("status")
                 .unwrap()
-                .as_str(),
-            "/* Comment at the end */",
-            "capture multi-line comment at the end of a SQL statement"
+                .get_default(),
+            Some("'pending'::public.\"OrderStatus\""),
+            "enum cast"
         );
 
-        // Test edge cases like empty comments or comments with only spaces
-        assert!(RE_COMMENTS.is_match("-- "), "empty single-line comment");
-        assert!(
-            RE_COMMENTS.is_match("  #     "),
-            "empty single-line comment with leading spaces"
+        assert_eq!(schema.get_relations().len(), 5, "relations");
+        let order_relation: &Relation = schema
+            .get_relations()
+            .iter()
+            .find(|r| r.get_origin_table() == "order_line" && r.get_refered_table() == "shop_order")
+            .unwrap();
+        assert_eq!(
+            order_relation.get_pairs_of_keys(),
+            &[(String::from("orderId"), String::from("id"))],
+            "quoted keys"
         );
-        assert!(RE_COMMENTS.is_match("/*  */"), "empty multi-line comment");
-        assert!(
-            RE_COMMENTS.is_match("/*\n   \n*/"),
-            "empty multi-line comment with newline characters"
+        assert_eq!(
+            order_relation.get_on_delete(),
+            Some(ReferentialAction::Cascade)
         );
-
-        // Ensure the comment is correctly captured even with different line breaks
         assert_eq!(
-            RE_COMMENTS
-                .captures("/*\nThis is a multi-line comment\n*/")
-                .unwrap()
-                .get(0)
-                .unwrap()
-                .as_str(),
-            "/*\nThis is a multi-line comment\n*/",
-            "capture multi-line comment with line breaks"
+            order_relation.get_on_update(),
+            Some(ReferentialAction::Cascade)
         );
+        assert!(schema
+            .get_relations()
+            .iter()
+            .any(|r| r.get_origin_table() == "address" && r.get_refered_table() == "UserAccount"));
     }
 }
@@ -0,0 +1,314 @@
+// Copyright ⓒ 2021-2024 LABEYE Loïc
+// This tool is distributed under the MIT License, check out [here](https://github.com/nag763/doteur/blob/main/LICENCE.MD).
+
+use std::fmt;
+
+use crate::schema::{Cardinality, Column, Index, Relation, Schema, Table};
+
+/// A DbmlFile object is used to render the schema as DBML, the language of dbdiagram.io
+/// and dbdocs
+pub struct DbmlFile<'a> {
+    /// The schema to render
+    schema: &'a Schema,
+}
+
+impl fmt::Display for DbmlFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let blocks: Vec<String> = self
+            .schema
+            .get_tables()
+            .iter()
+            .map(generate_table)
+            .collect();
+        write!(f, "{}", blocks.join("\n"))?;
+        if !self.schema.get_relations().is_empty() {
+            writeln!(f)?;
+        }
+        for relation in self.schema.get_relations() {
+            writeln!(f, "{}", generate_ref(relation))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> DbmlFile<'a> {
+    /// Creates a new DBML file from the given schema
+    pub fn new(schema: &'a Schema) -> DbmlFile<'a> {
+        DbmlFile { schema }
+    }
+}
+
+/// Quote the given identifier if it isn't a plain word
+fn quote(identifier: &str) -> String {
+    match !identifier.is_empty()
+        && identifier
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        true => identifier.to_string(),
+        false => format!("\"{}\"", identifier.replace('"', "\\\"")),
+    }
+}
+
+/// Quote the name of a table, its schema being written apart if it prefixes the name
+fn quote_table(table_name: &str) -> String {
+    match table_name.split_once('.') {
+        Some((schema, name)) => format!("{}.{}", quote(schema), quote(name)),
+        None => quote(table_name),
+    }
+}
+
+/// Format a SQL default value as a DBML one
+///
+/// Strings, numbers and the `true`, `false` and `null` keywords are kept as they are, any
+/// other value being considered as an expression.
+fn format_default(default: &str) -> String {
+    let is_string: bool = default.len() > 1 && default.starts_with('\'') && default.ends_with('\'');
+    let is_keyword: bool = ["true", "false", "null"].contains(&default.to_lowercase().as_str());
+    match is_string || is_keyword || default.parse::<f64>().is_ok() {
+        true => default.to_string(),
+        false => format!("`{}`", default.replace('`', "'")),
+    }
+}
+
+/// Format a comment as a DBML note, between single quotes
+fn format_note(note: &str) -> String {
+    format!("'{}'", note.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Generate the DBML line of a column
+///
+/// # Arguments
+///
+/// * `column` - The column to render
+/// * `single_pk` - Whether the table has a single primary key, the composite ones being
+///   declared as an index of the table
+fn generate_column(column: &Column, single_pk: bool) -> String {
+    let mut settings: Vec<String> = Vec::new();
+    if single_pk && column.is_primary_key() {
+        settings.push(String::from("pk"));
+    }
+    if !column.is_nullable() {
+        settings.push(String::from("not null"));
+    }
+    if column.is_unique() && !column.is_primary_key() {
+        settings.push(String::from("unique"));
+    }
+    if let Some(default) = column.get_default() {
+        settings.push(format!("default: {}", format_default(default)));
+    }
+    if let Some(comment) = column.get_comment() {
+        settings.push(format!("note: {}", format_note(comment)));
+    }
+    format!(
+        "  {} {}{}\n",
+        quote(column.get_name()),
+        quote(column.get_data_type()),
+        match settings.is_empty() {
+            true => String::new(),
+            false => format!(" [{}]", settings.join(", ")),
+        }
+    )
+}
+
+/// Generate the DBML block of a table
+fn generate_table(table: &Table) -> String {
+    let mut block: String = format!("Table {} {{\n", quote_table(table.get_name()));
+    let primary_key: Vec<&Column> = table.get_primary_key();
+    for column in table.get_columns() {
+        block.push_str(generate_column(column, primary_key.len() == 1).as_str());
+    }
+    // The composite primary key is declared along the other indexes
+    let indexes: Vec<String> = match primary_key.len() > 1 {
+        true => Some(format!(
+            "({}) [pk]",
+            primary_key
+                .iter()
+                .map(|column| quote(column.get_name()))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )),
+        false => None,
+    }
+    .into_iter()
+    .chain(table.get_indexes().iter().map(generate_index))
+    .collect();
+    if !indexes.is_empty() {
+        block.push_str(format!("\n  indexes {{\n    {}\n  }}\n", indexes.join("\n    ")).as_str());
+    }
+    if let Some(comment) = table.get_comment() {
+        block.push_str(format!("\n  Note: {}\n", format_note(comment)).as_str());
+    }
+    block.push_str("}\n");
+    block
+}
+
+/// Generate the DBML line of an index
+fn generate_index(index: &Index) -> String {
+    let columns: Vec<String> = index
+        .get_columns()
+        .iter()
+        .map(|column| match column.contains('(') {
+            // The expressions are written between backticks
+            true => format!("`{}`", column),
+            false => quote(column),
+        })
+        .collect();
+    let settings: Vec<String> = index
+        .is_unique()
+        .then(|| "unique".to_string())
+        .into_iter()
+        .chain(
+            index
+                .get_name()
+                .map(|name| format!("name: '{}'", name.replace('\'', "\\'"))),
+        )
+        .collect();
+    format!(
+        "{}{}",
+        match columns.as_slice() {
+            [column] => column.to_string(),
+            _ => format!("({})", columns.join(", ")),
+        },
+        match settings.is_empty() {
+            true => String::new(),
+            false => format!(" [{}]", settings.join(", ")),
+        }
+    )
+}
+
+/// Format the given keys as a DBML column reference
+fn format_keys(table: &str, keys: Vec<&str>) -> String {
+    match keys.as_slice() {
+        [key] => format!("{}.{}", quote_table(table), quote(key)),
+        _ => format!(
+            "{}.({})",
+            quote_table(table),
+            keys.iter()
+                .map(|key| quote(key))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Generate the DBML reference of a relation
+///
+/// Only the actions declared in the input are written in the output.
+fn generate_ref(relation: &Relation) -> String {
+    let mut settings: Vec<String> = Vec::new();
+    if let Some(on_delete) = relation.get_on_delete() {
+        settings.push(format!("delete: {}", on_delete.as_sql().to_lowercase()));
+    }
+    if let Some(on_update) = relation.get_on_update() {
+        settings.push(format!("update: {}", on_update.as_sql().to_lowercase()));
+    }
+    format!(
+        "Ref: {} {} {}{}",
+        format_keys(
+            relation.get_origin_table(),
+            relation
+                .get_pairs_of_keys()
+                .iter()
+                .map(|(origin_key, _)| origin_key.as_str())
+                .collect()
+        ),
+        match relation.get_cardinality() {
+            Cardinality::OneToOne => "-",
+            Cardinality::OneToMany => ">",
+        },
+        format_keys(
+            relation.get_refered_table(),
+            relation
+                .get_pairs_of_keys()
+                .iter()
+                .map(|(_, refered_key)| refered_key.as_str())
+                .collect()
+        ),
+        match settings.is_empty() {
+            true => String::new(),
+            false => format!(" [{}]", settings.join(", ")),
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::parse_schema;
+
+    #[test]
+    fn test_format_default() {
+        assert_eq!(format_default("'foo'"), "'foo'", "string");
+        assert_eq!(format_default("0.00"), "0.00", "number");
+        assert_eq!(format_default("NULL"), "NULL", "keyword");
+        assert_eq!(format_default("now()"), "`now()`", "expression");
+        assert_eq!(
+            format_default("'FR'::bpchar"),
+            "`'FR'::bpchar`",
+            "casted string"
+        );
+    }
+
+    #[test]
+    fn test_dbml_file() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE author (
+                id INT NOT NULL,
+                email VARCHAR(255) UNIQUE,
+                country CHAR(2) DEFAULT 'FR',
+                PRIMARY KEY (id)
+            );
+            CREATE TABLE book (
+                id INT PRIMARY KEY,
+                author_id INT,
+                FOREIGN KEY (author_id) REFERENCES author(id) ON DELETE CASCADE
+            );
+            COMMENT ON TABLE public.book IS 'The book''s editions are apart';
+            COMMENT ON COLUMN book.author_id IS 'Main author';
+            CREATE TABLE edition (
+                book_id INT,
+                author_id INT,
+                PRIMARY KEY (book_id, author_id),
+                UNIQUE KEY uk_edition (author_id, book_id),
+                FOREIGN KEY (book_id, author_id) REFERENCES book(id, author_id)
+            );",
+            None,
+        );
+        let output: String = DbmlFile::new(&schema).to_string();
+        assert!(
+            output.starts_with("Table author {\n  id INT [pk, not null]\n"),
+            "table"
+        );
+        assert!(
+            output.contains("  email \"VARCHAR(255)\" [unique]\n"),
+            "unique"
+        );
+        assert!(
+            output.contains("  country \"CHAR(2)\" [default: 'FR']\n"),
+            "default"
+        );
+        assert!(
+            output.contains("  author_id INT [note: 'Main author']\n"),
+            "column note"
+        );
+        assert!(
+            output.contains("\n  Note: 'The book\\'s editions are apart'\n}\n"),
+            "table note"
+        );
+        assert!(
+            output.contains("Ref: book.author_id > author.id [delete: cascade]\n"),
+            "cascade"
+        );
+        assert!(
+            output.contains("  indexes {\n    (book_id, author_id) [pk]\n    (author_id, book_id) [unique, name: 'uk_edition']\n  }\n"),
+            "composite primary key"
+        );
+        assert!(
+            output.contains("Ref: edition.(book_id, author_id) - book.(id, author_id)\n"),
+            "composite"
+        );
+    }
+}
@@ -0,0 +1,307 @@
+// Copyright ⓒ 2021-2024 LABEYE Loïc
+// This tool is distributed under the MIT License, check out [here](https://github.com/nag763/doteur/blob/main/LICENCE.MD).
+
+use serde::Serialize;
+
+use crate::schema::{Check, Column, Index, Relation, Schema, Table, View};
+
+/// Version of the JSON document, to increase on every breaking change of its structure
+pub(crate) const JSON_FORMAT_VERSION: u32 = 1;
+
+/// Root of the JSON document
+#[derive(Serialize)]
+pub struct JsonFile<'a> {
+    /// Version of the structure of the document
+    version: u32,
+    /// Tables of the schema
+    tables: Vec<JsonTable<'a>>,
+    /// Views of the schema
+    views: Vec<JsonView<'a>>,
+    /// Relations between the tables
+    relations: Vec<JsonRelation<'a>>,
+}
+
+/// A table as written in the JSON document
+#[derive(Serialize)]
+struct JsonTable<'a> {
+    name: &'a str,
+    schema: Option<&'a str>,
+    description: Option<&'a str>,
+    columns: Vec<JsonColumn<'a>>,
+    primary_key: Vec<&'a str>,
+    indexes: Vec<JsonIndex<'a>>,
+    checks: Vec<JsonCheck<'a>>,
+}
+
+/// An index as written in the JSON document
+#[derive(Serialize)]
+struct JsonIndex<'a> {
+    name: Option<&'a str>,
+    columns: Vec<&'a str>,
+    unique: bool,
+}
+
+/// A check constraint as written in the JSON document
+#[derive(Serialize)]
+struct JsonCheck<'a> {
+    name: Option<&'a str>,
+    expression: &'a str,
+}
+
+/// A view as written in the JSON document
+#[derive(Serialize)]
+struct JsonView<'a> {
+    name: &'a str,
+    columns: Vec<&'a str>,
+    dependencies: Vec<&'a str>,
+}
+
+/// A column as written in the JSON document
+#[derive(Serialize)]
+struct JsonColumn<'a> {
+    name: &'a str,
+    #[serde(rename = "type")]
+    data_type: &'a str,
+    nullable: bool,
+    default: Option<&'a str>,
+    primary_key: bool,
+    unique: bool,
+    auto_increment: bool,
+    generated: Option<&'a str>,
+    check: Option<&'a str>,
+    description: Option<&'a str>,
+    references: Option<JsonColumnReference<'a>>,
+}
+
+/// A column reference as written in the JSON document
+#[derive(Serialize)]
+struct JsonColumnReference<'a> {
+    table: &'a str,
+    column: &'a str,
+}
+
+/// A relation as written in the JSON document
+#[derive(Serialize)]
+struct JsonRelation<'a> {
+    table: &'a str,
+    columns: Vec<&'a str>,
+    referenced_table: &'a str,
+    referenced_columns: Vec<&'a str>,
+    on_delete: Option<&'static str>,
+    on_update: Option<&'static str>,
+    cardinality: &'static str,
+    optional: bool,
+}
+
+impl<'a> From<&'a Column> for JsonColumn<'a> {
+    fn from(column: &'a Column) -> Self {
+        JsonColumn {
+            name: column.get_name(),
+            data_type: column.get_data_type(),
+            nullable: column.is_nullable(),
+            default: column.get_default(),
+            primary_key: column.is_primary_key(),
+            unique: column.is_unique(),
+            auto_increment: column.is_auto_increment(),
+            generated: column.get_generated(),
+            check: column.get_check(),
+            description: column.get_comment(),
+            references: column
+                .get_foreign_key()
+                .map(|reference| JsonColumnReference {
+                    table: reference.get_table(),
+                    column: reference.get_column(),
+                }),
+        }
+    }
+}
+
+impl<'a> From<&'a Index> for JsonIndex<'a> {
+    fn from(index: &'a Index) -> Self {
+        JsonIndex {
+            name: index.get_name(),
+            columns: index.get_columns().iter().map(String::as_str).collect(),
+            unique: index.is_unique(),
+        }
+    }
+}
+
+impl<'a> From<&'a Check> for JsonCheck<'a> {
+    fn from(check: &'a Check) -> Self {
+        JsonCheck {
+            name: check.get_name(),
+            expression: check.get_expression(),
+        }
+    }
+}
+
+impl<'a> From<&'a Table> for JsonTable<'a> {
+    fn from(table: &'a Table) -> Self {
+        JsonTable {
+            name: table.get_name(),
+            schema: table.get_schema(),
+            description: table.get_comment(),
+            columns: table.get_columns().iter().map(JsonColumn::from).collect(),
+            primary_key: table
+                .get_primary_key()
+                .iter()
+                .map(|column| column.get_name())
+                .collect(),
+            indexes: table.get_indexes().iter().map(JsonIndex::from).collect(),
+            checks: table.get_checks().iter().map(JsonCheck::from).collect(),
+        }
+    }
+}
+
+impl<'a> From<&'a View> for JsonView<'a> {
+    fn from(view: &'a View) -> Self {
+        JsonView {
+            name: view.get_name(),
+            columns: view.get_columns().iter().map(String::as_str).collect(),
+            dependencies: view.get_dependencies().iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl<'a> From<&'a Relation> for JsonRelation<'a> {
+    fn from(relation: &'a Relation) -> Self {
+        JsonRelation {
+            table: relation.get_origin_table(),
+            columns: relation
+                .get_pairs_of_keys()
+                .iter()
+                .map(|(origin_key, _)| origin_key.as_str())
+                .collect(),
+            referenced_table: relation.get_refered_table(),
+            referenced_columns: relation
+                .get_pairs_of_keys()
+                .iter()
+                .map(|(_, refered_key)| refered_key.as_str())
+                .collect(),
+            on_delete: relation.get_on_delete().map(|action| action.as_sql()),
+            on_update: relation.get_on_update().map(|action| action.as_sql()),
+            cardinality: relation.get_cardinality().as_str(),
+            optional: relation.is_optional(),
+        }
+    }
+}
+
+impl<'a> JsonFile<'a> {
+    /// Creates a new JSON document from the given schema
+    pub fn new(schema: &'a Schema) -> JsonFile<'a> {
+        JsonFile {
+            version: JSON_FORMAT_VERSION,
+            tables: schema.get_tables().iter().map(JsonTable::from).collect(),
+            views: schema.get_views().iter().map(JsonView::from).collect(),
+            relations: schema
+                .get_relations()
+                .iter()
+                .map(JsonRelation::from)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::parse_schema;
+
+    #[test]
+    fn test_json_file() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE author (id INT PRIMARY KEY AUTO_INCREMENT, name VARCHAR(255) NOT NULL DEFAULT 'anonymous' COMMENT 'Pen name') COMMENT='Writers of the books';
+            CREATE TABLE book (
+                id INT PRIMARY KEY,
+                author_id INT,
+                KEY idx_author (author_id),
+                CONSTRAINT positive_id CHECK (id > 0),
+                FOREIGN KEY (author_id) REFERENCES author(id) ON DELETE CASCADE
+            );
+            CREATE VIEW book_author AS SELECT book.id, author.name AS author FROM book JOIN author ON author.id = book.author_id;",
+            None,
+        );
+        let value: serde_json::Value = serde_json::to_value(JsonFile::new(&schema)).unwrap();
+        assert_eq!(value["version"], JSON_FORMAT_VERSION, "version");
+        assert_eq!(
+            value["tables"][0],
+            serde_json::json!({
+                "name": "author",
+                "schema": null,
+                "description": "Writers of the books",
+                "columns": [
+                    {
+                        "name": "id",
+                        "type": "INT",
+                        "nullable": false,
+                        "default": null,
+                        "primary_key": true,
+                        "unique": false,
+                        "auto_increment": true,
+                        "generated": null,
+                        "check": null,
+                        "description": null,
+                        "references": null
+                    },
+                    {
+                        "name": "name",
+                        "type": "VARCHAR(255)",
+                        "nullable": false,
+                        "default": "'anonymous'",
+                        "primary_key": false,
+                        "unique": false,
+                        "auto_increment": false,
+                        "generated": null,
+                        "check": null,
+                        "description": "Pen name",
+                        "references": null
+                    }
+                ],
+                "primary_key": ["id"],
+                "indexes": [],
+                "checks": []
+            }),
+            "table"
+        );
+        assert_eq!(
+            value["tables"][1]["indexes"],
+            serde_json::json!([{"name": "idx_author", "columns": ["author_id"], "unique": false}]),
+            "indexes"
+        );
+        assert_eq!(
+            value["tables"][1]["checks"],
+            serde_json::json!([{"name": "positive_id", "expression": "id > 0"}]),
+            "checks"
+        );
+        assert_eq!(
+            value["tables"][1]["columns"][1]["references"],
+            serde_json::json!({"table": "author", "column": "id"}),
+            "column reference"
+        );
+        assert_eq!(
+            value["views"],
+            serde_json::json!([{
+                "name": "book_author",
+                "columns": ["id", "author"],
+                "dependencies": ["book", "author"]
+            }]),
+            "views"
+        );
+        assert_eq!(
+            value["relations"],
+            serde_json::json!([{
+                "table": "book",
+                "columns": ["author_id"],
+                "referenced_table": "author",
+                "referenced_columns": ["id"],
+                "on_delete": "CASCADE",
+                "on_update": null,
+                "cardinality": "one_to_many",
+                "optional": true
+            }]),
+            "relations"
+        );
+    }
+}
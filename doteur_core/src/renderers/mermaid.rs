@@ -0,0 +1,248 @@
+// Copyright ⓒ 2021-2024 LABEYE Loïc
+// This tool is distributed under the MIT License, check out [here](https://github.com/nag763/doteur/blob/main/LICENCE.MD).
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::get_relationship_notation;
+use crate::schema::{Column, Relation, Schema, Table};
+
+const RELATE_TO_EMOJI: &str = "➡";
+
+/// A MermaidFile object is used to render the schema as a Mermaid `erDiagram`
+pub struct MermaidFile<'a> {
+    /// The schema to render
+    schema: &'a Schema,
+    /// The names of the entities, by the name of their table
+    entities: HashMap<&'a str, String>,
+}
+
+impl fmt::Display for MermaidFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "erDiagram")?;
+        for table in self.schema.get_tables() {
+            write!(
+                f,
+                "{}",
+                generate_entity(table, &self.entities[table.get_name()])
+            )?;
+        }
+        for relation in self.schema.get_relations() {
+            writeln!(f, "{}", generate_relationship(relation, &self.entities))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> MermaidFile<'a> {
+    /// Creates a new mermaid file from the given schema
+    ///
+    /// Every table, including the ones only refered by the relations, is given an entity name
+    /// of its own, the names that would clash once sanitized being suffixed by a number.
+    pub fn new(schema: &'a Schema) -> MermaidFile<'a> {
+        let mut entities: HashMap<&str, String> = HashMap::new();
+        let mut taken: HashSet<String> = HashSet::new();
+        let names =
+            schema
+                .get_tables()
+                .iter()
+                .map(|table| table.get_name())
+                .chain(schema.get_relations().iter().flat_map(|relation| {
+                    [relation.get_origin_table(), relation.get_refered_table()]
+                }));
+        for name in names {
+            if entities.contains_key(name) {
+                continue;
+            }
+            let base: String = get_entity_name(name);
+            let mut entity: String = base.clone();
+            let mut suffix: usize = 2;
+            while !taken.insert(entity.clone()) {
+                entity = format!("{}_{}", base, suffix);
+                suffix += 1;
+            }
+            entities.insert(name, entity);
+        }
+        MermaidFile { schema, entities }
+    }
+}
+
+/// Transform a SQL identifier or type into one accepted by Mermaid
+///
+/// Mermaid only accepts alphanumeric characters, hyphens, underscores, parenthesis and
+/// brackets, the other characters are then replaced by underscores.
+fn sanitize(input: &str) -> String {
+    let mut sanitized: String = String::new();
+    for c in input.chars().filter(|c| !matches!(c, '`' | '"' | '\'')) {
+        let c: char = match c.is_alphanumeric() || matches!(c, '-' | '(' | ')' | '[' | ']') {
+            true => c,
+            false => '_',
+        };
+        // Avoid sequences of underscores
+        if !(c == '_' && sanitized.ends_with('_')) {
+            sanitized.push(c);
+        }
+    }
+    let sanitized: &str = sanitized.trim_matches('_');
+    match sanitized.chars().next() {
+        Some(c) if c.is_alphabetic() => sanitized.to_string(),
+        Some(_) => format!("_{}", sanitized),
+        None => String::from("unknown"),
+    }
+}
+
+/// Transform the name of a table into the name of its entity
+///
+/// The schema of the table is separated from its name by a hyphen, which can't be part of an
+/// unquoted SQL identifier, so that `sales.order` doesn't clash with `sales_order`.
+fn get_entity_name(name: &str) -> String {
+    sanitize(&name.replace('.', "-"))
+}
+
+/// Returns the Mermaid key markers of a column
+fn get_key_markers(column: &Column) -> Vec<&'static str> {
+    let mut markers: Vec<&str> = Vec::new();
+    if column.is_primary_key() {
+        markers.push("PK");
+    }
+    if column.is_foreign_key() {
+        markers.push("FK");
+    }
+    if column.is_unique() && !column.is_primary_key() {
+        markers.push("UK");
+    }
+    markers
+}
+
+/// Generate the Mermaid entity of a table
+///
+/// # Arguments
+///
+/// * `table` - The table to render
+/// * `name` - The name of the entity of the table
+fn generate_entity(table: &Table, name: &str) -> String {
+    let mut entity: String = format!("    {} {{\n", name);
+    for column in table.get_columns() {
+        let markers: Vec<&str> = get_key_markers(column);
+        entity.push_str(
+            format!(
+                "        {} {}{}\n",
+                sanitize(column.get_data_type()),
+                sanitize(column.get_name()),
+                match markers.is_empty() {
+                    true => String::new(),
+                    false => format!(" {}", markers.join(", ")),
+                }
+            )
+            .as_str(),
+        );
+    }
+    entity.push_str("    }\n");
+    entity
+}
+
+/// Generate the Mermaid relationship of a relation
+///
+/// # Arguments
+///
+/// * `relation` - The relation to render
+/// * `entities` - The names of the entities, by the name of their table
+fn generate_relationship(relation: &Relation, entities: &HashMap<&str, String>) -> String {
+    format!(
+        "    {} {} {} : \"{}\"",
+        entities[relation.get_refered_table()],
+        get_relationship_notation(relation),
+        entities[relation.get_origin_table()],
+        relation
+            .get_pairs_of_keys()
+            .iter()
+            .map(|(origin_key, refered_key)| format!(
+                "{} {} {}",
+                origin_key.replace('"', "'"),
+                RELATE_TO_EMOJI,
+                refered_key.replace('"', "'")
+            ))
+            // The action executed on update is described after the keys
+            .chain(relation.get_on_update_hint())
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::parse_schema;
+
+    #[test]
+    fn test_sanitize() {
+        assert_eq!(sanitize("foo"), "foo", "nothing to sanitize");
+        assert_eq!(sanitize("INT(10)"), "INT(10)", "parenthesis");
+        assert_eq!(sanitize("decimal(10,2)"), "decimal(10_2)", "coma");
+        assert_eq!(
+            sanitize("character varying(40)"),
+            "character_varying(40)",
+            "spaces"
+        );
+        assert_eq!(
+            sanitize("public.\"OrderStatus\""),
+            "public_OrderStatus",
+            "quotes"
+        );
+        assert_eq!(sanitize("2fa"), "_2fa", "leading digit");
+        assert_eq!(sanitize(""), "unknown", "empty");
+    }
+
+    #[test]
+    fn test_mermaid_file() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE author (id INT PRIMARY KEY, email VARCHAR(255) UNIQUE);
+            CREATE TABLE book (
+                id INT PRIMARY KEY,
+                author_id INT NOT NULL,
+                editor_id INT UNIQUE,
+                FOREIGN KEY (author_id) REFERENCES author(id) ON DELETE CASCADE,
+                FOREIGN KEY (editor_id) REFERENCES author(id) ON DELETE SET NULL
+            );",
+            None,
+        );
+        let output: String = MermaidFile::new(&schema).to_string();
+        assert!(output.starts_with("erDiagram\n"), "header");
+        assert!(output.contains("        INT id PK\n"), "pk");
+        assert!(output.contains("        VARCHAR(255) email UK\n"), "unique");
+        assert!(output.contains("        INT author_id FK\n"), "fk");
+        assert!(
+            output.contains("    author ||--o{ book : \"author_id ➡ id\"\n"),
+            "cascade"
+        );
+        assert!(
+            output.contains("    author |o..o| book : \"editor_id ➡ id\"\n"),
+            "set null"
+        );
+    }
+
+    #[test]
+    fn test_mermaid_file_name_clashes() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE sales.order (id INT PRIMARY KEY);
+            CREATE TABLE billing.order (id INT PRIMARY KEY);
+            CREATE TABLE sales_order (id INT PRIMARY KEY);
+            CREATE TABLE \"sales order\" (
+                id INT PRIMARY KEY,
+                order_id INT NOT NULL REFERENCES sales.order(id)
+            );",
+            None,
+        );
+        let output: String = MermaidFile::new(&schema).to_string();
+        assert!(output.contains("    sales-order {\n"), "schema");
+        assert!(output.contains("    billing-order {\n"), "schema");
+        assert!(output.contains("    sales_order {\n"), "underscore");
+        assert!(output.contains("    sales_order_2 {\n"), "suffixed");
+        assert!(
+            output.contains("    sales-order ||..o{ sales_order_2 : \"order_id ➡ id\"\n"),
+            "relation"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright ⓒ 2021-2024 LABEYE Loïc
+// This tool is distributed under the MIT License, check out [here](https://github.com/nag763/doteur/blob/main/LICENCE.MD).
+
+pub(crate) mod dbml;
+pub(crate) mod json;
+pub(crate) mod mermaid;
+pub(crate) mod plantuml;
+#[cfg(feature = "native_svg")]
+pub(crate) mod svg;
+
+use crate::schema::{Cardinality, ReferentialAction, Relation};
+
+/// Returns the crow's foot notation of a relationship
+///
+/// The refered side is optional when the refering keys are nullable, and the refering side
+/// holds one row at most when the relation is a one to one. The cascade relations are
+/// rendered as identifying relationships, meaning the refering rows can't exist without the
+/// refered one.
+///
+/// The notation is shared by Mermaid and PlantUML.
+fn get_relationship_notation(relation: &Relation) -> String {
+    format!(
+        "{}{}{}",
+        match relation.is_optional() {
+            true => "|o",
+            false => "||",
+        },
+        match relation.get_on_delete() {
+            Some(ReferentialAction::Cascade) => "--",
+            _ => "..",
+        },
+        match relation.get_cardinality() {
+            Cardinality::OneToOne => "o|",
+            Cardinality::OneToMany => "o{",
+        }
+    )
+}
@@ -0,0 +1,239 @@
+// Copyright ⓒ 2021-2024 LABEYE Loïc
+// This tool is distributed under the MIT License, check out [here](https://github.com/nag763/doteur/blob/main/LICENCE.MD).
+
+use std::fmt;
+
+use super::get_relationship_notation;
+use crate::schema::{Column, Index, Relation, Schema, Table, View};
+
+const RELATE_TO_EMOJI: &str = "➡";
+
+/// A PlantUmlFile object is used to render the schema as a PlantUML entity relationship diagram
+pub struct PlantUmlFile<'a> {
+    /// The schema to render
+    schema: &'a Schema,
+}
+
+impl fmt::Display for PlantUmlFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "@startuml")?;
+        writeln!(f, "hide circle")?;
+        writeln!(f, "skinparam linetype ortho")?;
+        for table in self.schema.get_tables() {
+            write!(f, "\n{}", generate_entity(table))?;
+        }
+        for view in self.schema.get_views() {
+            write!(f, "\n{}", generate_view(view))?;
+        }
+        if !self.schema.get_relations().is_empty() || !self.schema.get_views().is_empty() {
+            writeln!(f)?;
+        }
+        for relation in self.schema.get_relations() {
+            writeln!(f, "{}", generate_relationship(relation))?;
+        }
+        // The views depend on the tables and views they select from
+        for view in self.schema.get_views() {
+            for dependency in view.get_dependencies().iter().filter(|dependency| {
+                self.schema.get_table(dependency).is_some()
+                    || self.schema.get_view(dependency).is_some()
+            }) {
+                writeln!(
+                    f,
+                    "{} ..> {}",
+                    get_alias(view.get_name()),
+                    get_alias(dependency)
+                )?;
+            }
+        }
+        writeln!(f, "@enduml")
+    }
+}
+
+impl<'a> PlantUmlFile<'a> {
+    /// Creates a new PlantUML file from the given schema
+    pub fn new(schema: &'a Schema) -> PlantUmlFile<'a> {
+        PlantUmlFile { schema }
+    }
+}
+
+/// Get the alias of a table
+///
+/// The names of the tables can contain characters PlantUML doesn't accept as identifiers,
+/// the entities are then declared with their real name as label and an alias.
+fn get_alias(table_name: &str) -> String {
+    let alias: String = table_name
+        .chars()
+        .map(|c| match c.is_alphanumeric() {
+            true => c,
+            false => '_',
+        })
+        .collect();
+    match alias.chars().next() {
+        Some(c) if c.is_alphabetic() || c == '_' => alias,
+        _ => format!("_{}", alias),
+    }
+}
+
+/// Generate the line of a column within its entity
+fn generate_column(column: &Column) -> String {
+    let mut line: String = format!(
+        "    {}{} : {}",
+        // Mandatory columns are starred
+        match column.is_nullable() {
+            true => "",
+            false => "* ",
+        },
+        column.get_name(),
+        column.get_data_type()
+    );
+    if column.is_primary_key() {
+        line.push_str(" <<PK>>");
+    }
+    if column.is_foreign_key() {
+        line.push_str(" <<FK>>");
+    }
+    line.push('\n');
+    line
+}
+
+/// Generate the PlantUML entity of a table
+///
+/// The primary key columns are listed first, separated from the others by a line.
+fn generate_entity(table: &Table) -> String {
+    let mut entity: String = format!(
+        "entity \"{}\" as {} {{\n",
+        table.get_name(),
+        get_alias(table.get_name())
+    );
+    let (keys, others): (Vec<&Column>, Vec<&Column>) = table
+        .get_columns()
+        .iter()
+        .partition(|column| column.is_primary_key());
+    for column in keys.iter() {
+        entity.push_str(generate_column(column).as_str());
+    }
+    if !keys.is_empty() && !others.is_empty() {
+        entity.push_str("    --\n");
+    }
+    for column in others.iter() {
+        entity.push_str(generate_column(column).as_str());
+    }
+    if !table.get_indexes().is_empty() {
+        entity.push_str("    .. indexes ..\n");
+    }
+    for index in table.get_indexes() {
+        entity.push_str(generate_index(index).as_str());
+    }
+    entity.push_str("}\n");
+    entity
+}
+
+/// Generate the PlantUML entity of a view, marked with the view stereotype
+fn generate_view(view: &View) -> String {
+    let mut entity: String = format!(
+        "entity \"{}\" as {} <<view>> {{\n",
+        view.get_name(),
+        get_alias(view.get_name())
+    );
+    for column in view.get_columns() {
+        entity.push_str(format!("    {}\n", column).as_str());
+    }
+    entity.push_str("}\n");
+    entity
+}
+
+/// Generate the PlantUML line of an index
+fn generate_index(index: &Index) -> String {
+    format!(
+        "    {}{} ({})\n",
+        index
+            .get_name()
+            .map_or(String::new(), |name| format!("{} : ", name)),
+        match index.is_unique() {
+            true => "UNIQUE",
+            false => "INDEX",
+        },
+        index.get_columns().join(", ")
+    )
+}
+
+/// Generate the PlantUML relationship of a relation
+fn generate_relationship(relation: &Relation) -> String {
+    format!(
+        "{} {} {} : \"{}\"",
+        get_alias(relation.get_refered_table()),
+        get_relationship_notation(relation),
+        get_alias(relation.get_origin_table()),
+        relation
+            .get_pairs_of_keys()
+            .iter()
+            .map(|(origin_key, refered_key)| format!(
+                "{} {} {}",
+                origin_key, RELATE_TO_EMOJI, refered_key
+            ))
+            // The action executed on update is described after the keys
+            .chain(relation.get_on_update_hint())
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::parse_schema;
+
+    #[test]
+    fn test_get_alias() {
+        assert_eq!(get_alias("foo"), "foo", "nothing to replace");
+        assert_eq!(get_alias("foo-bar baz"), "foo_bar_baz", "replaced");
+        assert_eq!(get_alias("2fa"), "_2fa", "leading digit");
+    }
+
+    #[test]
+    fn test_plantuml_file() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE author (id INT PRIMARY KEY, name VARCHAR(255));
+            CREATE TABLE book (
+                id INT PRIMARY KEY,
+                author_id INT NOT NULL,
+                FOREIGN KEY (author_id) REFERENCES author(id) ON DELETE CASCADE
+            );",
+            None,
+        );
+        let output: String = PlantUmlFile::new(&schema).to_string();
+        assert!(output.starts_with("@startuml\n"), "header");
+        assert!(output.ends_with("@enduml\n"), "footer");
+        assert!(
+            output.contains(
+                "entity \"author\" as author {\n    * id : INT <<PK>>\n    --\n    name : VARCHAR(255)\n}\n"
+            ),
+            "entity"
+        );
+        assert!(
+            output.contains("    * author_id : INT <<FK>>\n"),
+            "foreign key"
+        );
+        assert!(
+            output.contains("author ||--o{ book : \"author_id ➡ id\"\n"),
+            "relation"
+        );
+
+        let schema: Schema = parse_schema(
+            "CREATE TABLE author (id INT PRIMARY KEY);
+            CREATE VIEW author_ids AS SELECT id FROM author;",
+            None,
+        );
+        let output: String = PlantUmlFile::new(&schema).to_string();
+        assert!(
+            output.contains("entity \"author_ids\" as author_ids <<view>> {\n    id\n}\n"),
+            "view"
+        );
+        assert!(
+            output.contains("author_ids ..> author\n"),
+            "view dependency"
+        );
+    }
+}
@@ -0,0 +1,957 @@
+// Copyright ⓒ 2021-2024 LABEYE Loïc
+// This tool is distributed under the MIT License, check out [here](https://github.com/nag763/doteur/blob/main/LICENCE.MD).
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::schema::{
+    Cardinality, Check, Column, Index, ReferentialAction, Relation, Schema, Table, Verbosity, View,
+};
+
+const RELATE_TO_EMOJI: &str = "➡";
+const PK_EMOJI: &str = "🔑";
+const FK_EMOJI: &str = "🗝️";
+
+/// Approximative width of a character with the font size used
+const CHAR_WIDTH: f64 = 7.0;
+/// Height of the header of a table
+const HEADER_HEIGHT: f64 = 28.0;
+/// Height of a row of a table
+const ROW_HEIGHT: f64 = 20.0;
+/// Inner padding of the tables
+const PADDING: f64 = 8.0;
+/// Horizontal space between two tables of the same layer
+const HORIZONTAL_GAP: f64 = 40.0;
+/// Vertical space between two layers
+const VERTICAL_GAP: f64 = 80.0;
+/// Margin around the drawing
+const MARGIN: f64 = 20.0;
+/// Number of sweeps made to reduce the crossings between layers
+const ORDERING_SWEEPS: usize = 4;
+
+/// Colors used in the output
+struct Theme {
+    background: Option<&'static str>,
+    table: &'static str,
+    header: &'static str,
+    view_header: &'static str,
+    stub: &'static str,
+    text: &'static str,
+    edge: &'static str,
+}
+
+impl Theme {
+    /// Returns the theme matching the DOT output one
+    fn new(dark_mode: bool) -> Theme {
+        match dark_mode {
+            true => Theme {
+                background: Some("black"),
+                table: "#333333",
+                header: "#1a1a1a",
+                view_header: "darkslategray",
+                stub: "#999999",
+                text: "white",
+                edge: "white",
+            },
+            false => Theme {
+                background: None,
+                table: "#f2f2f2",
+                header: "indigo",
+                view_header: "darkcyan",
+                stub: "#7f7f7f",
+                text: "black",
+                edge: "black",
+            },
+        }
+    }
+}
+
+/// A table, a view or a stub once laid out
+struct SvgNode<'a> {
+    /// Name of the rendered table or view
+    name: &'a str,
+    /// Texts of the rows, the columns being followed by the indexes
+    rows: Vec<(String, String)>,
+    /// Number of rows describing the columns
+    columns: usize,
+    /// Whether the node is a view
+    view: bool,
+    /// Whether the node stands for a table that isn't rendered
+    stub: bool,
+    /// Comment of the table, shown on hover
+    comment: Option<&'a str>,
+    /// Comments of the columns, shown on hover of their rows
+    comments: Vec<Option<&'a str>>,
+    /// Layer of the table, the refering tables being above the refered ones
+    layer: usize,
+    /// Left coordinate
+    x: f64,
+    /// Top coordinate
+    y: f64,
+    /// Width of the table
+    width: f64,
+    /// Width of the column holding the names of the columns
+    name_width: f64,
+    /// Height of the table
+    height: f64,
+}
+
+impl SvgNode<'_> {
+    fn center_x(&self) -> f64 {
+        self.x + self.width / 2.0
+    }
+
+    fn center_y(&self) -> f64 {
+        self.y + self.height / 2.0
+    }
+}
+
+/// A SvgFile object is used to render the schema as a SVG image without Graphviz
+///
+/// The tables are laid out in layers, following the Sugiyama method : the relations are
+/// first made acyclic, the tables are then assigned to layers so that every refering table
+/// is above the table it refers to, and the tables of each layer are finally ordered to
+/// reduce the crossings between the relations.
+pub struct SvgFile<'a> {
+    /// The schema whose relations are drawn
+    schema: &'a Schema,
+    /// The tables, the views and the stubs once laid out
+    nodes: Vec<SvgNode<'a>>,
+    /// Index of the nodes by table name
+    indexes: HashMap<&'a str, usize>,
+    /// Whether a legend is drawn under the tables
+    legend: bool,
+    /// Whether the relations are drawn with the crow's foot notation
+    crows_foot: bool,
+    /// Colors of the output
+    theme: Theme,
+}
+
+impl fmt::Display for SvgFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (width, height): (f64, f64) = self.get_size();
+        writeln!(
+            f,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"Roboto, sans-serif\" font-size=\"12\">",
+            width, height
+        )?;
+        writeln!(
+            f,
+            "<!-- This file has been generated with doteur, enjoy! -->"
+        )?;
+        writeln!(f, "<defs>")?;
+        writeln!(f, "<marker id=\"normal\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\"><path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"{}\"/></marker>", self.theme.edge)?;
+        writeln!(f, "<marker id=\"dot\" viewBox=\"0 0 10 10\" refX=\"9\" refY=\"5\" markerWidth=\"7\" markerHeight=\"7\"><circle cx=\"5\" cy=\"5\" r=\"4\" fill=\"{}\"/></marker>", self.theme.edge)?;
+        writeln!(f, "<marker id=\"odot\" viewBox=\"0 0 10 10\" refX=\"9\" refY=\"5\" markerWidth=\"7\" markerHeight=\"7\"><circle cx=\"5\" cy=\"5\" r=\"4\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"/></marker>", self.theme.edge)?;
+        writeln!(f, "<marker id=\"empty\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\"><path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"{}\" stroke=\"{}\"/></marker>", self.theme.background.unwrap_or("white"), self.theme.edge)?;
+        // The crow's foot markers are drawn with the refered table on their right side, and
+        // reversed when placed at the start of a path
+        writeln!(f, "<marker id=\"one\" viewBox=\"0 0 20 10\" refX=\"20\" refY=\"5\" markerWidth=\"16\" markerHeight=\"8\" orient=\"auto-start-reverse\"><path d=\"M 0 5 L 20 5 M 10 0 L 10 10 M 14 0 L 14 10\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"/></marker>", self.theme.edge)?;
+        writeln!(f, "<marker id=\"zero_or_one\" viewBox=\"0 0 20 10\" refX=\"20\" refY=\"5\" markerWidth=\"16\" markerHeight=\"8\" orient=\"auto-start-reverse\"><path d=\"M 8 5 L 20 5 M 14 0 L 14 10\" fill=\"none\" stroke=\"{0}\" stroke-width=\"1.5\"/><circle cx=\"4.5\" cy=\"5\" r=\"3.5\" fill=\"{1}\" stroke=\"{0}\" stroke-width=\"1.5\"/></marker>", self.theme.edge, self.theme.background.unwrap_or("white"))?;
+        writeln!(f, "<marker id=\"zero_or_many\" viewBox=\"0 0 20 10\" refX=\"20\" refY=\"5\" markerWidth=\"16\" markerHeight=\"8\" orient=\"auto-start-reverse\"><path d=\"M 8 5 L 20 5 M 12 5 L 20 0 M 12 5 L 20 10\" fill=\"none\" stroke=\"{0}\" stroke-width=\"1.5\"/><circle cx=\"4.5\" cy=\"5\" r=\"3.5\" fill=\"{1}\" stroke=\"{0}\" stroke-width=\"1.5\"/></marker>", self.theme.edge, self.theme.background.unwrap_or("white"))?;
+        writeln!(f, "</defs>")?;
+        if let Some(background) = self.theme.background {
+            writeln!(
+                f,
+                "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+                background
+            )?;
+        }
+        for node in self.nodes.iter() {
+            write!(f, "{}", self.generate_table(node))?;
+        }
+        for relation in self.schema.get_relations() {
+            write!(f, "{}", self.generate_relation(relation))?;
+        }
+        for view in self.schema.get_views() {
+            for dependency in view.get_dependencies() {
+                write!(f, "{}", self.generate_view_dependency(view, dependency))?;
+            }
+        }
+        if self.legend {
+            write!(f, "{}", self.generate_legend(height))?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+impl<'a> SvgFile<'a> {
+    /// Creates a new SVG file from the given schema, laying out its tables
+    pub fn new(
+        schema: &'a Schema,
+        legend: bool,
+        dark_mode: bool,
+        crows_foot: bool,
+        verbosity: Verbosity,
+    ) -> SvgFile<'a> {
+        let mut nodes: Vec<SvgNode> = schema
+            .get_tables()
+            .iter()
+            .map(|table| measure_table(table, verbosity))
+            .chain(schema.get_views().iter().map(measure_view))
+            .chain(
+                schema
+                    .get_stubs()
+                    .iter()
+                    .map(|stub| measure_stub(stub.as_str())),
+            )
+            .collect();
+        let indexes: HashMap<&str, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.name, i))
+            .collect();
+        let edges: Vec<(usize, usize)> = get_edges(schema, &indexes);
+        let layers: Vec<usize> = assign_layers(nodes.len(), &edges);
+        for (node, layer) in nodes.iter_mut().zip(layers) {
+            node.layer = layer;
+        }
+        let ordering: Vec<Vec<usize>> = order_layers(&nodes, &edges);
+        place_nodes(&mut nodes, &ordering, &edges);
+        SvgFile {
+            schema,
+            nodes,
+            indexes,
+            legend,
+            crows_foot,
+            theme: Theme::new(dark_mode),
+        }
+    }
+
+    /// Returns the size of the drawing
+    fn get_size(&self) -> (f64, f64) {
+        let width: f64 = self
+            .nodes
+            .iter()
+            .map(|node| node.x + node.width)
+            .fold(0.0, f64::max)
+            + MARGIN;
+        let mut height: f64 = self
+            .nodes
+            .iter()
+            .map(|node| node.y + node.height)
+            .fold(0.0, f64::max)
+            + MARGIN;
+        if self.legend {
+            height += self.get_legend_rows().len() as f64 * ROW_HEIGHT + MARGIN;
+        }
+        (width.max(2.0 * MARGIN), height.max(2.0 * MARGIN))
+    }
+
+    /// Generate the SVG group of a table or a view
+    fn generate_table(&self, node: &SvgNode) -> String {
+        // The stubs only show their name, greyed out
+        if node.stub {
+            return format!(
+                "<g class=\"stub\" id=\"{0}\">\n<rect x=\"{1}\" y=\"{2}\" width=\"{3}\" height=\"{4}\" fill=\"{5}\" stroke=\"{6}\" stroke-dasharray=\"4 2\"/>\n<text x=\"{7}\" y=\"{8}\" fill=\"{6}\" font-style=\"italic\" text-anchor=\"middle\">{0}</text>\n</g>\n",
+                escape(node.name),
+                node.x,
+                node.y,
+                node.width,
+                node.height,
+                self.theme.table,
+                self.theme.stub,
+                node.center_x(),
+                node.y + HEADER_HEIGHT / 2.0 + 4.0,
+            );
+        }
+        let mut group: String = format!(
+            "<g class=\"{11}\" id=\"{0}\">\n<rect x=\"{1}\" y=\"{2}\" width=\"{3}\" height=\"{4}\" fill=\"{5}\" stroke=\"{6}\"/>\n<rect x=\"{1}\" y=\"{2}\" width=\"{3}\" height=\"{7}\" fill=\"{8}\" stroke=\"{6}\"/>\n<text x=\"{9}\" y=\"{10}\" fill=\"white\" font-weight=\"bold\" text-anchor=\"middle\">{0}</text>\n",
+            escape(node.name),
+            node.x,
+            node.y,
+            node.width,
+            node.height,
+            self.theme.table,
+            self.theme.edge,
+            HEADER_HEIGHT,
+            match node.view {
+                true => self.theme.view_header,
+                false => self.theme.header,
+            },
+            node.center_x(),
+            node.y + HEADER_HEIGHT / 2.0 + 4.0,
+            match node.view {
+                true => "view",
+                false => "table",
+            }
+        );
+        if let Some(comment) = node.comment {
+            group.push_str(format!("<title>{}</title>\n", escape(comment)).as_str());
+        }
+        // The indexes are separated from the columns by a line
+        if node.rows.len() > node.columns {
+            let y: f64 = node.y + HEADER_HEIGHT + ROW_HEIGHT * node.columns as f64;
+            group.push_str(
+                format!(
+                    "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\"/>\n",
+                    node.x,
+                    y,
+                    node.x + node.width,
+                    y,
+                    self.theme.edge
+                )
+                .as_str(),
+            );
+        }
+        for (i, (name, definition)) in node.rows.iter().enumerate() {
+            let y: f64 = node.y + HEADER_HEIGHT + ROW_HEIGHT * (i as f64) + ROW_HEIGHT / 2.0 + 4.0;
+            // The comment of the column is shown on hover of its texts
+            let title: String = match node.comments.get(i).copied().flatten() {
+                Some(comment) => format!("<title>{}</title>", escape(comment)),
+                None => String::new(),
+            };
+            group.push_str(
+                format!(
+                    "<text x=\"{}\" y=\"{}\" fill=\"{}\" font-weight=\"bold\">{}{}</text>\n",
+                    node.x + PADDING,
+                    y,
+                    self.theme.text,
+                    escape(name),
+                    title
+                )
+                .as_str(),
+            );
+            group.push_str(
+                format!(
+                    "<text x=\"{}\" y=\"{}\" fill=\"{}\">{}{}</text>\n",
+                    node.x + PADDING + node.name_width + PADDING,
+                    y,
+                    self.theme.text,
+                    escape(definition),
+                    title
+                )
+                .as_str(),
+            );
+        }
+        group.push_str("</g>\n");
+        group
+    }
+
+    /// Generate the SVG path of a relation
+    fn generate_relation(&self, relation: &Relation) -> String {
+        let (origin, refered): (&SvgNode, &SvgNode) = match (
+            self.indexes.get(relation.get_origin_table()),
+            self.indexes.get(relation.get_refered_table()),
+        ) {
+            (Some(origin), Some(refered)) => (&self.nodes[*origin], &self.nodes[*refered]),
+            _ => return String::new(),
+        };
+        let path: String = match std::ptr::eq(origin, refered) {
+            // A table refering itself is drawn as a loop on its right side
+            true => {
+                let (x, y): (f64, f64) = (origin.x + origin.width, origin.y + HEADER_HEIGHT);
+                format!(
+                    "M {0} {1} C {2} {1} {2} {3} {0} {3}",
+                    x,
+                    y,
+                    x + VERTICAL_GAP / 2.0,
+                    y + HEADER_HEIGHT
+                )
+            }
+            false => get_curve(origin, refered),
+        };
+        let (label_x, label_y): (f64, f64) = match std::ptr::eq(origin, refered) {
+            true => (
+                origin.x + origin.width + VERTICAL_GAP / 2.0,
+                origin.y + HEADER_HEIGHT * 1.5,
+            ),
+            false => (
+                (origin.center_x() + refered.center_x()) / 2.0,
+                (origin.center_y() + refered.center_y()) / 2.0,
+            ),
+        };
+        // The cascading updates are drawn thicker, as in the DOT output
+        let stroke_width: f64 = match relation.get_on_update() {
+            Some(ReferentialAction::Cascade) => 2.0,
+            _ => 1.0,
+        };
+        let mut label: String = relation
+            .get_pairs_of_keys()
+            .iter()
+            .map(|(origin_key, refered_key)| {
+                format!("{} {} {}", origin_key, RELATE_TO_EMOJI, refered_key)
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+        if let Some(hint) = relation.get_on_update_hint() {
+            label.push_str(format!(" ({})", hint).as_str());
+        }
+        // With the crow's foot notation, the relations not deleted along the refered row are
+        // dashed, as in the DOT output
+        let markers: String = match self.crows_foot {
+            true => {
+                let (marker_end, marker_start): (&str, &str) = get_crows_foot_markers(relation);
+                format!(
+                    "marker-start=\"url(#{})\" marker-end=\"url(#{})\"{}",
+                    marker_start,
+                    marker_end,
+                    match relation.get_on_delete() {
+                        Some(ReferentialAction::Cascade) => "",
+                        _ => " stroke-dasharray=\"6 3\"",
+                    }
+                )
+            }
+            false => format!(
+                "marker-end=\"url(#{})\"",
+                get_marker(relation.get_on_delete())
+            ),
+        };
+        format!(
+            "<g class=\"relation\">\n<path d=\"{0}\" fill=\"none\" stroke=\"{1}\" stroke-width=\"{7}\" {2}/>\n<text x=\"{3}\" y=\"{4}\" fill=\"{1}\" font-style=\"italic\" text-anchor=\"middle\" stroke=\"{5}\" stroke-width=\"3\" paint-order=\"stroke\">{6}</text>\n</g>\n",
+            path,
+            self.theme.edge,
+            markers,
+            label_x,
+            label_y,
+            self.theme.background.unwrap_or("white"),
+            escape(&label),
+            stroke_width
+        )
+    }
+
+    /// Generate the SVG path of the dependency of a view toward a table or another view
+    fn generate_view_dependency(&self, view: &View, dependency: &str) -> String {
+        match (
+            self.indexes.get(view.get_name()),
+            self.indexes.get(dependency),
+        ) {
+            (Some(origin), Some(refered)) if origin != refered => format!(
+                "<g class=\"dependency\">\n<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-dasharray=\"6 3\" marker-end=\"url(#empty)\"/>\n</g>\n",
+                get_curve(&self.nodes[*origin], &self.nodes[*refered]),
+                self.theme.edge
+            ),
+            _ => String::new(),
+        }
+    }
+
+    /// Returns the rows of the legend, as the markers at the start and at the end of the path,
+    /// the label, the stroke width and whether the path is dashed
+    #[allow(clippy::type_complexity)]
+    fn get_legend_rows(
+        &self,
+    ) -> Vec<(Option<&'static str>, &'static str, &'static str, f64, bool)> {
+        match self.crows_foot {
+            true => vec![
+                (Some("zero_or_many"), "one", "composition", 1.0, false),
+                (Some("zero_or_many"), "one", "association", 1.0, true),
+                (Some("zero_or_one"), "one", "one to one", 1.0, true),
+                (Some("zero_or_many"), "zero_or_one", "optional", 1.0, true),
+                (Some("zero_or_many"), "one", "on update cascade", 2.0, true),
+                (None, "empty", "view dependency", 1.0, true),
+            ],
+            false => vec![
+                (None, "dot", "composition", 1.0, false),
+                (None, "odot", "aggregation", 1.0, false),
+                (None, "normal", "association", 1.0, false),
+                (None, "normal", "on update cascade", 2.0, false),
+                (None, "empty", "view dependency", 1.0, true),
+            ],
+        }
+    }
+
+    /// Generate the legend describing the types of relations
+    fn generate_legend(&self, height: f64) -> String {
+        let mut legend: String = String::from("<g class=\"legend\">\n");
+        let rows = self.get_legend_rows();
+        let count: f64 = rows.len() as f64;
+        for (i, (marker_start, marker_end, label, stroke_width, dashed)) in
+            rows.into_iter().enumerate()
+        {
+            let y: f64 = height - MARGIN - ROW_HEIGHT * (count - 0.5 - i as f64);
+            legend.push_str(format!(
+                "<path d=\"M {0} {1} L {2} {1}\" stroke=\"{3}\" stroke-width=\"{8}\"{9} marker-end=\"url(#{4})\"{10}/>\n<text x=\"{5}\" y=\"{6}\" fill=\"{3}\">{7}</text>\n",
+                MARGIN,
+                y,
+                MARGIN + 60.0,
+                self.theme.edge,
+                marker_end,
+                MARGIN + 70.0,
+                y + 4.0,
+                label,
+                stroke_width,
+                marker_start.map_or(String::new(), |marker| format!(" marker-start=\"url(#{})\"", marker)),
+                match dashed {
+                    true => " stroke-dasharray=\"6 3\"",
+                    false => "",
+                }
+            ).as_str());
+        }
+        legend.push_str("</g>\n");
+        legend
+    }
+}
+
+/// Returns the SVG path of a curve going from a node to another one
+fn get_curve(origin: &SvgNode, refered: &SvgNode) -> String {
+    let ((start_x, start_y), (end_x, end_y)): ((f64, f64), (f64, f64)) =
+        match origin.center_y() <= refered.center_y() {
+            true => (
+                (origin.center_x(), origin.y + origin.height),
+                (refered.center_x(), refered.y),
+            ),
+            false => (
+                (origin.center_x(), origin.y),
+                (refered.center_x(), refered.y + refered.height),
+            ),
+        };
+    let middle_y: f64 = (start_y + end_y) / 2.0;
+    format!(
+        "M {} {} C {} {} {} {} {} {}",
+        start_x, start_y, start_x, middle_y, end_x, middle_y, end_x, end_y
+    )
+}
+
+/// Escape the characters having a meaning in XML
+fn escape(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Returns the markers of the relation in the crow's foot notation, the one on the refered
+/// side first, as the arrowheads and arrowtails of the DOT output
+fn get_crows_foot_markers(relation: &Relation) -> (&'static str, &'static str) {
+    (
+        match relation.is_optional() {
+            true => "zero_or_one",
+            false => "one",
+        },
+        match relation.get_cardinality() {
+            Cardinality::OneToOne => "zero_or_one",
+            Cardinality::OneToMany => "zero_or_many",
+        },
+    )
+}
+
+/// Returns the marker matching the on delete action, as the arrowheads of the DOT output
+fn get_marker(on_delete: Option<ReferentialAction>) -> &'static str {
+    match on_delete {
+        Some(ReferentialAction::SetNull) => "odot",
+        Some(ReferentialAction::Cascade) => "dot",
+        _ => "normal",
+    }
+}
+
+/// Returns the texts of the row of a column, as written in the DOT output
+fn get_row(column: &Column, verbosity: Verbosity) -> (String, String) {
+    let name: String = match (column.is_primary_key(), column.is_foreign_key()) {
+        (true, true) => format!("{} {}{}", column.get_name(), PK_EMOJI, FK_EMOJI),
+        (true, false) => format!("{} {}", column.get_name(), PK_EMOJI),
+        (false, true) => format!("{} {}", column.get_name(), FK_EMOJI),
+        (false, false) => column.get_name().to_string(),
+    };
+    let definition: String = match column.get_foreign_key() {
+        Some(reference) => format!(
+            "Refers to {}[{}]",
+            reference.get_table(),
+            reference.get_column()
+        ),
+        None => column.get_description(verbosity),
+    };
+    (name, definition)
+}
+
+/// Returns the texts of the row of an index, as written in the DOT output
+fn get_index_row(index: &Index) -> (String, String) {
+    (
+        index.get_name().unwrap_or_default().to_string(),
+        format!(
+            "{} ({})",
+            match index.is_unique() {
+                true => "UNIQUE",
+                false => "INDEX",
+            },
+            index.get_columns().join(", ")
+        ),
+    )
+}
+
+/// Returns the texts of the row of a check constraint, as written in the DOT output
+fn get_check_row(check: &Check) -> (String, String) {
+    (
+        check.get_name().unwrap_or_default().to_string(),
+        format!("CHECK ({})", check.get_expression()),
+    )
+}
+
+/// Returns the texts of the rows of a table, the columns being followed by the indexes and,
+/// with the full verbosity, the check constraints
+fn get_rows(table: &Table, verbosity: Verbosity) -> Vec<(String, String)> {
+    let checks: &[Check] = match verbosity {
+        Verbosity::Full => table.get_checks(),
+        _ => &[],
+    };
+    table
+        .get_columns()
+        .iter()
+        .map(|column| get_row(column, verbosity))
+        .chain(table.get_indexes().iter().map(get_index_row))
+        .chain(checks.iter().map(get_check_row))
+        .collect()
+}
+
+/// Approximate the width of a text, the emojis being twice as large as the other characters
+fn text_width(text: &str) -> f64 {
+    text.chars()
+        .map(|c| match c.len_utf8() > 2 {
+            true => 2.0,
+            false => 1.0,
+        })
+        .sum::<f64>()
+        * CHAR_WIDTH
+}
+
+/// Measure a table or a view from the texts of its rows
+fn measure<'a>(
+    name: &'a str,
+    rows: Vec<(String, String)>,
+    columns: usize,
+    view: bool,
+) -> SvgNode<'a> {
+    let name_width: f64 = rows
+        .iter()
+        .map(|(column, _)| text_width(column))
+        .fold(0.0, f64::max);
+    let definition_width: f64 = rows
+        .iter()
+        .map(|(_, definition)| text_width(definition))
+        .fold(0.0, f64::max);
+    let height: f64 = HEADER_HEIGHT + ROW_HEIGHT * rows.len() as f64 + PADDING / 2.0;
+    SvgNode {
+        name,
+        rows,
+        columns,
+        view,
+        stub: false,
+        comment: None,
+        comments: Vec::new(),
+        layer: 0,
+        x: 0.0,
+        y: 0.0,
+        width: (name_width + definition_width + 3.0 * PADDING)
+            .max(text_width(name) + 2.0 * PADDING),
+        name_width,
+        height,
+    }
+}
+
+/// Measure a table, its size depending on the texts of its rows
+fn measure_table(table: &Table, verbosity: Verbosity) -> SvgNode<'_> {
+    let mut node: SvgNode = measure(
+        table.get_name(),
+        get_rows(table, verbosity),
+        table.get_columns().len(),
+        false,
+    );
+    node.comment = table.get_comment();
+    node.comments = table
+        .get_columns()
+        .iter()
+        .map(|column| column.get_comment())
+        .collect();
+    node
+}
+
+/// Measure a view, whose columns are only described by their name
+fn measure_view(view: &View) -> SvgNode<'_> {
+    measure(
+        view.get_name(),
+        view.get_columns()
+            .iter()
+            .map(|column| (column.to_string(), String::new()))
+            .collect(),
+        view.get_columns().len(),
+        true,
+    )
+}
+
+/// Measure a stub, only showing the name of the table it stands for
+fn measure_stub(name: &str) -> SvgNode<'_> {
+    let mut node: SvgNode = measure(name, Vec::new(), 0, false);
+    node.stub = true;
+    node.height = HEADER_HEIGHT;
+    node
+}
+
+/// Returns the distinct relations between two different tables, as indexes of nodes
+///
+/// The dependencies of the views are considered as relations as well.
+fn get_edges(schema: &Schema, indexes: &HashMap<&str, usize>) -> Vec<(usize, usize)> {
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    let relations = schema
+        .get_relations()
+        .iter()
+        .map(|relation| (relation.get_origin_table(), relation.get_refered_table()))
+        .chain(schema.get_views().iter().flat_map(|view| {
+            view.get_dependencies()
+                .iter()
+                .map(move |dependency| (view.get_name(), dependency.as_str()))
+        }));
+    for (origin, refered) in relations {
+        if let (Some(origin), Some(refered)) = (indexes.get(origin), indexes.get(refered)) {
+            if origin != refered && !edges.contains(&(*origin, *refered)) {
+                edges.push((*origin, *refered));
+            }
+        }
+    }
+    edges
+}
+
+/// Assign a layer to every node
+///
+/// The edges closing a cycle are ignored, then each node is placed one layer under the
+/// lowest node refering it.
+fn assign_layers(size: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    // Depth first search to find the edges closing a cycle
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); size];
+    for (origin, refered) in edges {
+        successors[*origin].push(*refered);
+    }
+    let mut state: Vec<u8> = vec![0; size];
+    let mut acyclic_edges: Vec<(usize, usize)> = Vec::new();
+    for root in 0..size {
+        if state[root] != 0 {
+            continue;
+        }
+        let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+        state[root] = 1;
+        while let Some((node, next)) = stack.pop() {
+            if let Some(successor) = successors[node].get(next) {
+                stack.push((node, next + 1));
+                match state[*successor] {
+                    0 => {
+                        acyclic_edges.push((node, *successor));
+                        state[*successor] = 1;
+                        stack.push((*successor, 0));
+                    }
+                    // The successor is on the stack, the edge closes a cycle
+                    1 => (),
+                    _ => acyclic_edges.push((node, *successor)),
+                }
+            } else {
+                state[node] = 2;
+            }
+        }
+    }
+
+    // Longest path layering, processed in topological order
+    let mut incoming: Vec<usize> = vec![0; size];
+    for (_, refered) in acyclic_edges.iter() {
+        incoming[*refered] += 1;
+    }
+    let mut layers: Vec<usize> = vec![0; size];
+    let mut queue: Vec<usize> = (0..size).filter(|i| incoming[*i] == 0).collect();
+    while let Some(node) = queue.pop() {
+        for (_, refered) in acyclic_edges.iter().filter(|(origin, _)| *origin == node) {
+            layers[*refered] = layers[*refered].max(layers[node] + 1);
+            incoming[*refered] -= 1;
+            if incoming[*refered] == 0 {
+                queue.push(*refered);
+            }
+        }
+    }
+    layers
+}
+
+/// Order the nodes within their layers to reduce the crossings
+///
+/// Each sweep sorts the nodes of a layer by the barycenter of the positions of their
+/// neighbours, going downward then upward.
+fn order_layers(nodes: &[SvgNode], edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let number_of_layers: usize = nodes.iter().map(|node| node.layer + 1).max().unwrap_or(0);
+    let mut ordering: Vec<Vec<usize>> = vec![Vec::new(); number_of_layers];
+    for (i, node) in nodes.iter().enumerate() {
+        ordering[node.layer].push(i);
+    }
+    let mut positions: Vec<f64> = vec![0.0; nodes.len()];
+    let update_positions = |ordering: &Vec<Vec<usize>>, positions: &mut Vec<f64>| {
+        for layer in ordering.iter() {
+            for (i, node) in layer.iter().enumerate() {
+                positions[*node] = (i as f64 + 0.5) / layer.len() as f64;
+            }
+        }
+    };
+    update_positions(&ordering, &mut positions);
+    for sweep in 0..ORDERING_SWEEPS {
+        let downward: bool = sweep % 2 == 0;
+        let layer_indexes: Vec<usize> = match downward {
+            true => (1..number_of_layers).collect(),
+            false => (0..number_of_layers.saturating_sub(1)).rev().collect(),
+        };
+        for layer_index in layer_indexes {
+            let mut barycenters: Vec<(usize, f64)> = ordering[layer_index]
+                .iter()
+                .map(|node| {
+                    let neighbours: Vec<f64> = edges
+                        .iter()
+                        .filter_map(|(origin, refered)| match (*origin, *refered) {
+                            (o, r) if o == *node => Some(r),
+                            (o, r) if r == *node => Some(o),
+                            _ => None,
+                        })
+                        .filter(|neighbour| match downward {
+                            true => nodes[*neighbour].layer < layer_index,
+                            false => nodes[*neighbour].layer > layer_index,
+                        })
+                        .map(|neighbour| positions[neighbour])
+                        .collect();
+                    match neighbours.is_empty() {
+                        true => (*node, positions[*node]),
+                        false => (
+                            *node,
+                            neighbours.iter().sum::<f64>() / neighbours.len() as f64,
+                        ),
+                    }
+                })
+                .collect();
+            barycenters.sort_by(|a, b| a.1.total_cmp(&b.1));
+            ordering[layer_index] = barycenters.into_iter().map(|(node, _)| node).collect();
+            update_positions(&ordering, &mut positions);
+        }
+    }
+    ordering
+}
+
+/// Give their coordinates to the nodes
+///
+/// The first layer is placed from the left, the nodes of the next layers being placed as
+/// close as possible to the center of the nodes they are related to in the previous layers.
+fn place_nodes(nodes: &mut [SvgNode], ordering: &[Vec<usize>], edges: &[(usize, usize)]) {
+    let mut y: f64 = MARGIN;
+    for (layer_index, layer) in ordering.iter().enumerate() {
+        let mut min_x: f64 = MARGIN;
+        for node in layer.iter() {
+            let neighbours: Vec<f64> = edges
+                .iter()
+                .filter_map(|(origin, refered)| match (*origin, *refered) {
+                    (o, r) if o == *node => Some(r),
+                    (o, r) if r == *node => Some(o),
+                    _ => None,
+                })
+                .filter(|neighbour| nodes[*neighbour].layer < layer_index)
+                .map(|neighbour| nodes[neighbour].center_x())
+                .collect();
+            let x: f64 = match neighbours.is_empty() {
+                true => min_x,
+                false => (neighbours.iter().sum::<f64>() / neighbours.len() as f64
+                    - nodes[*node].width / 2.0)
+                    .max(min_x),
+            };
+            nodes[*node].x = x;
+            nodes[*node].y = y;
+            min_x = x + nodes[*node].width + HORIZONTAL_GAP;
+        }
+        y += layer
+            .iter()
+            .map(|node| nodes[*node].height)
+            .fold(0.0, f64::max)
+            + VERTICAL_GAP;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::parse_schema;
+    use crate::restriction::Restriction;
+
+    #[test]
+    fn test_assign_layers() {
+        assert_eq!(assign_layers(3, &[(0, 1), (1, 2)]), vec![0, 1, 2], "chain");
+        assert_eq!(
+            assign_layers(3, &[(0, 2), (1, 2), (0, 1)]),
+            vec![0, 1, 2],
+            "longest path"
+        );
+        assert_eq!(
+            assign_layers(3, &[(0, 1), (1, 2), (2, 0)]),
+            vec![0, 1, 2],
+            "cycle"
+        );
+        assert_eq!(assign_layers(2, &[]), vec![0, 0], "no edges");
+    }
+
+    #[test]
+    fn test_order_layers() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE a (id INT, c_id INT, FOREIGN KEY (c_id) REFERENCES d(id));
+            CREATE TABLE b (id INT, c_id INT, FOREIGN KEY (c_id) REFERENCES c(id));
+            CREATE TABLE c (id INT);
+            CREATE TABLE d (id INT);",
+            None,
+        );
+        let svg: SvgFile = SvgFile::new(&schema, false, false, false, Verbosity::Full);
+        let x = |name: &str| svg.nodes[svg.indexes[name]].x;
+        assert!(x("a") < x("b"), "first layer keeps the declaration order");
+        assert!(x("d") < x("c"), "second layer follows the first one");
+        assert!(
+            svg.nodes[svg.indexes["a"]].y < svg.nodes[svg.indexes["d"]].y,
+            "refering tables are above"
+        );
+    }
+
+    #[test]
+    fn test_svg_file() {
+        let schema: Schema = parse_schema(
+            "CREATE TABLE author (id INT PRIMARY KEY, name VARCHAR(255) DEFAULT '<none>');
+            CREATE TABLE book (
+                id INT PRIMARY KEY,
+                author_id INT,
+                FOREIGN KEY (author_id) REFERENCES author(id) ON DELETE CASCADE
+            );",
+            None,
+        );
+        let output: String = SvgFile::new(&schema, true, false, false, Verbosity::Full).to_string();
+        assert!(output.starts_with("<svg xmlns"), "header");
+        assert!(output.ends_with("</svg>\n"), "footer");
+        assert_eq!(output.matches("class=\"table\"").count(), 2, "tables");
+        assert!(output.contains("DEFAULT '&lt;none&gt;'"), "escaped");
+        assert!(output.contains("marker-end=\"url(#dot)\""), "cascade");
+        assert!(output.contains("author_id ➡ id"), "label");
+        assert!(output.contains("class=\"legend\""), "legend");
+
+        let schema: Schema = parse_schema(
+            "CREATE TABLE author (id INT PRIMARY KEY, name VARCHAR(255));
+            CREATE TABLE book (
+                id INT PRIMARY KEY,
+                author_id INT,
+                FOREIGN KEY (author_id) REFERENCES author(id) ON DELETE CASCADE
+            );
+            CREATE VIEW author_names AS SELECT name FROM author;
+            COMMENT ON TABLE author IS 'Writers';
+            COMMENT ON COLUMN book.author_id IS 'Main <author>';",
+            None,
+        );
+        let output: String = SvgFile::new(&schema, false, false, true, Verbosity::Full).to_string();
+        assert_eq!(output.matches("class=\"view\"").count(), 1, "view");
+        assert!(output.contains("<title>Writers</title>"), "table comment");
+        assert!(
+            output.contains("<title>Main &lt;author&gt;</title>"),
+            "column comment"
+        );
+        assert!(
+            output.contains("marker-end=\"url(#empty)\""),
+            "view dependency"
+        );
+        assert!(
+            output.contains("marker-start=\"url(#zero_or_many)\" marker-end=\"url(#zero_or_one)\""),
+            "crow's foot"
+        );
+
+        let schema: Schema = parse_schema(
+            "CREATE TABLE author (id INT PRIMARY KEY);
+            CREATE TABLE book (id INT PRIMARY KEY, author_id INT REFERENCES author(id));",
+            Some(&Restriction::new_exclusion(vec![String::from("author")]).with_stubs(true)),
+        );
+        let output: String =
+            SvgFile::new(&schema, false, false, false, Verbosity::Full).to_string();
+        assert_eq!(output.matches("class=\"table\"").count(), 1, "table");
+        assert!(output.contains("<g class=\"stub\" id=\"author\">"), "stub");
+        assert!(output.contains("author_id ➡ id"), "relation to the stub");
+    }
+}
@@ -1,30 +1,63 @@
 // Copyright ⓒ 2021-2024 LABEYE Loïc
 // This tool is distributed under the MIT License, check out [here](https://github.com/nag763/doteur/blob/main/LICENCE.MD).
 
+use log::warn;
 use regex::Regex;
 
+/// The prefix of the patterns written as regular expressions
+const REGEX_PREFIX: &str = "re:";
+
 /// ReSearchType indicates if the regex should be made according to the fact they match the
 /// given regexs, or not.
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum ReSearchType {
-    /// If the regex is matched, the value will be true
+    /// The tables matching the regex are kept, if any regex of this type is given
     Inclusive,
-    /// If the regex is not matched, the value will be true
+    /// The tables matching the regex are removed, even if they are included
     Exclusive,
+    /// The columns matching the regex, prefixed by the name of their table, are removed
+    ColumnExclusive,
 }
 
-/// Transform a string with easy regex contents to a more complex regex
+/// Transform a pattern into a regex
+///
+/// The pattern is either a simple one, where only the `*` is a wildcard and the whole name has
+/// to match, or a regular expression prefixed by `re:`.
 ///
 /// # Arguments
 ///
 /// * `input` - The string to transform into regex.
-fn str_to_regex(input: &str) -> Result<regex::Regex, regex::Error> {
-    if input.is_empty() {
+///
+/// # Example
+///
+/// ```
+/// use doteur_core::restriction::str_to_regex;
+/// assert!(str_to_regex("user*").unwrap().is_match("user_role"));
+/// assert!(!str_to_regex("a.b").unwrap().is_match("axb"));
+/// assert!(str_to_regex("re:^(user|role)s?$").unwrap().is_match("roles"));
+/// assert!(str_to_regex("re:(").is_err());
+/// ```
+pub fn str_to_regex(input: &str) -> Result<regex::Regex, regex::Error> {
+    if input.is_empty() || input == REGEX_PREFIX {
         return Err(regex::Error::Syntax(
             "Can't create an empty regex".to_string(),
         ));
     }
-    let regex: Regex = Regex::new(format!("^{}$", input.replace('*', ".*")).as_str())?;
+    if let Some(expression) = input.strip_prefix(REGEX_PREFIX) {
+        return Regex::new(expression);
+    }
+    // Everything but the wildcards is matched literally
+    let regex: Regex = Regex::new(
+        format!(
+            "^{}$",
+            input
+                .split('*')
+                .map(regex::escape)
+                .collect::<Vec<String>>()
+                .join(".*")
+        )
+        .as_str(),
+    )?;
     Ok(regex)
 }
 
@@ -41,14 +74,21 @@ macro_rules! matches_optionable_restriction {
 
 /// A restriction represents a condition to render or not the given table.
 ///
-/// A restriction can either be inclusive (will match only what matches the expressions)
-/// or exclusive (will only match what doesn't match the expressions)
+/// A restriction can be inclusive (will match only what matches the expressions), exclusive
+/// (will only match what doesn't match the expressions), or both, the excluded tables being
+/// removed from the included ones. It can also hide the columns of the tables, or focus on a
+/// table, only keeping the tables related to it once the whole input has been parsed.
+///
+/// By default, the relations toward the tables that don't match the restriction are dropped.
+/// They can be kept instead, the refered tables being then rendered as stubs.
 #[derive(Clone)]
 pub struct Restriction {
-    /// The list of regexs
-    regexs: Vec<Regex>,
-    /// The type of restriction to apply
-    re_search_type: ReSearchType,
+    /// The list of regexs, along the type of restriction they apply
+    regexs: Vec<(Regex, ReSearchType)>,
+    /// The focused table and the maximal number of relations to it, if any
+    focus: Option<(String, usize)>,
+    /// Whether the relations toward the tables that don't match the restriction are kept
+    stubs: bool,
 }
 
 impl Restriction {
@@ -59,16 +99,28 @@ impl Restriction {
     /// * `re_string` - The trivial regexs as string
     /// * `re_search_type` - How the regexs should be matches
     fn new(re_string: Vec<String>, re_search_type: ReSearchType) -> Restriction {
-        let mut regexs: Vec<Regex> = Vec::new();
-        re_string.iter().for_each(|element| {
-            if let Ok(value) = str_to_regex(element) {
-                regexs.push(value)
-            }
-        });
         Restriction {
-            regexs,
-            re_search_type,
+            regexs: Vec::new(),
+            focus: None,
+            stubs: false,
         }
+        .with_regexs(re_string, re_search_type)
+    }
+
+    /// Adds the given regexs to the restriction, the invalid ones being ignored
+    ///
+    /// # Arguments
+    ///
+    /// * `re_string` - The trivial regexs as string
+    /// * `re_search_type` - How the regexs should be matches
+    fn with_regexs(mut self, re_string: Vec<String>, re_search_type: ReSearchType) -> Restriction {
+        re_string
+            .iter()
+            .for_each(|element| match str_to_regex(element) {
+                Ok(value) => self.regexs.push((value, re_search_type)),
+                Err(e) => warn!("The pattern {} has been ignored : {}", element, e),
+            });
+        self
     }
 
     /// Creates a new restriction in inclusive mode
@@ -81,7 +133,7 @@ impl Restriction {
     ///
     /// ```
     /// use doteur_core::restriction::Restriction;
-    /// Restriction::new_inclusion(vec![String::from(".*hell")]);
+    /// Restriction::new_inclusion(vec![String::from("*hell")]);
     /// ```
     pub fn new_inclusion(re_string: Vec<String>) -> Restriction {
         Restriction::new(re_string, ReSearchType::Inclusive)
@@ -97,12 +149,129 @@ impl Restriction {
     ///
     /// ```
     /// use doteur_core::restriction::Restriction;
-    /// Restriction::new_exclusion(vec![String::from(".*hell")]);
+    /// Restriction::new_exclusion(vec![String::from("re:.*hell")]);
     /// ```
     pub fn new_exclusion(re_string: Vec<String>) -> Restriction {
         Restriction::new(re_string, ReSearchType::Exclusive)
     }
 
+    /// Excludes the given tables, even if they are included by the restriction
+    ///
+    /// # Arguments
+    ///
+    /// * `re_string` - The trivial regexs as string
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doteur_core::restriction::Restriction;
+    /// let rest = Restriction::new_inclusion(vec![String::from("user*")])
+    ///     .with_exclusion(vec![String::from("user_log")]);
+    /// assert!(rest.clone().verify_table_name("user_role"));
+    /// assert!(!rest.clone().verify_table_name("user_log"));
+    /// assert!(!rest.verify_table_name("role"));
+    /// ```
+    pub fn with_exclusion(self, re_string: Vec<String>) -> Restriction {
+        self.with_regexs(re_string, ReSearchType::Exclusive)
+    }
+
+    /// Excludes the given columns from every table
+    ///
+    /// The columns are matched prefixed by the name of their table, such as `user.created_at`.
+    ///
+    /// # Arguments
+    ///
+    /// * `re_string` - The trivial regexs as string
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doteur_core::restriction::Restriction;
+    /// let rest = Restriction::new_inclusion(vec![])
+    ///     .with_column_exclusion(vec![String::from("*.created_at")]);
+    /// assert!(!rest.verify_column_name("user", "created_at"));
+    /// assert!(rest.verify_column_name("user", "created_by"));
+    /// ```
+    pub fn with_column_exclusion(self, re_string: Vec<String>) -> Restriction {
+        self.with_regexs(re_string, ReSearchType::ColumnExclusive)
+    }
+
+    /// Focus the restriction on the given table
+    ///
+    /// Only the tables within the given number of relations of the focused table, whatever
+    /// the direction of the relations, will match the restriction.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_name` - The name of the focused table
+    /// * `depth` - The maximal number of relations between the focused table and the others
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doteur_core::restriction::Restriction;
+    /// let rest = Restriction::new_inclusion(vec![]).with_focus("orders", 2);
+    /// assert_eq!(rest.get_focus(), Some(("orders", 2)));
+    /// ```
+    pub fn with_focus(mut self, table_name: &str, depth: usize) -> Restriction {
+        self.focus = Some((table_name.to_string(), depth));
+        self
+    }
+
+    /// Returns the focused table and the maximal number of relations to it, if any
+    pub fn get_focus(&self) -> Option<(&str, usize)> {
+        self.focus
+            .as_ref()
+            .map(|(table_name, depth)| (table_name.as_str(), *depth))
+    }
+
+    /// Keep the relations toward the tables that don't match the restriction or not
+    ///
+    /// # Arguments
+    ///
+    /// * `stubs` - Whether the relations toward the tables that don't match the restriction are kept
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doteur_core::restriction::Restriction;
+    /// let rest = Restriction::new_exclusion(vec![String::from("customer")]).with_stubs(true);
+    /// assert!(rest.verify_relation("orders", "customer"));
+    /// ```
+    pub fn with_stubs(mut self, stubs: bool) -> Restriction {
+        self.stubs = stubs;
+        self
+    }
+
+    /// Returns whether the relations toward the tables that don't match the restriction are kept
+    pub fn has_stubs(&self) -> bool {
+        self.stubs
+    }
+
+    /// Checks if the relation between the given tables matches the restriction
+    ///
+    /// The refering table has to match the restriction, while the refered one only has to if
+    /// the relations toward the tables that don't match it aren't kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin_table` - The refering table of the relation
+    /// * `refered_table` - The refered table of the relation
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doteur_core::restriction::Restriction;
+    /// let rest = Restriction::new_exclusion(vec![String::from("customer")]);
+    /// assert!(!rest.verify_relation("orders", "customer"));
+    /// assert!(!rest.verify_relation("customer", "orders"));
+    /// assert!(rest.with_stubs(true).verify_relation("orders", "customer"));
+    /// ```
+    pub fn verify_relation(&self, origin_table: &str, refered_table: &str) -> bool {
+        self.clone().verify_table_name(origin_table)
+            && (self.stubs || self.clone().verify_table_name(refered_table))
+    }
+
     /// Checks if the given inputs matches the restriction
     ///
     /// # Arguments
@@ -129,15 +298,37 @@ impl Restriction {
     /// );
     /// ```
     pub fn verify_table_name(self, table_name: &str) -> bool {
-        if !self.regexs.is_empty() {
-            match self.re_search_type {
-                ReSearchType::Inclusive => self.regexs.iter().any(|e| e.is_match(table_name)),
-                ReSearchType::Exclusive => !self.regexs.iter().any(|e| e.is_match(table_name)),
-            }
-        } else {
-            // If the array is empty
-            true
-        }
+        let matches = |re_search_type: ReSearchType| {
+            self.regexs
+                .iter()
+                .filter(|(_, regex_type)| *regex_type == re_search_type)
+                .map(|(regex, _)| regex.is_match(table_name))
+                .collect::<Vec<bool>>()
+        };
+        let inclusions: Vec<bool> = matches(ReSearchType::Inclusive);
+        // If no table is included, every table is
+        (inclusions.is_empty() || inclusions.contains(&true))
+            && !matches(ReSearchType::Exclusive).contains(&true)
+    }
+
+    /// Checks if the given column of a table matches the restriction
+    ///
+    /// # Arguments
+    ///
+    /// * `table_name` - The table of the column
+    /// * `column_name` - The column to verify with the given restriction
+    pub fn verify_column_name(&self, table_name: &str, column_name: &str) -> bool {
+        let name: String = format!("{}.{}", table_name, column_name);
+        !self.regexs.iter().any(|(regex, re_search_type)| {
+            *re_search_type == ReSearchType::ColumnExclusive && regex.is_match(&name)
+        })
+    }
+
+    /// Returns whether the restriction excludes some columns
+    pub fn has_column_exclusions(&self) -> bool {
+        self.regexs
+            .iter()
+            .any(|(_, re_search_type)| *re_search_type == ReSearchType::ColumnExclusive)
     }
 }
 
@@ -171,9 +362,24 @@ mod tests {
         );
         assert_eq!(
             str_to_regex("dollar$*").unwrap().as_str(),
-            Regex::new("^dollar$.*$").unwrap().as_str(),
+            Regex::new("^dollar\\$.*$").unwrap().as_str(),
             "edgy normal use case"
         );
+        assert_eq!(
+            str_to_regex("public.user*").unwrap().as_str(),
+            Regex::new("^public\\.user.*$").unwrap().as_str(),
+            "escaped"
+        );
+        assert_eq!(
+            str_to_regex("re:^user_(role|right)s?$").unwrap().as_str(),
+            Regex::new("^user_(role|right)s?$").unwrap().as_str(),
+            "regular expression"
+        );
+        assert!(str_to_regex("re:").is_err(), "empty regular expression");
+        assert!(
+            str_to_regex("re:[a-").is_err(),
+            "invalid regular expression"
+        );
     }
 
     #[test]
@@ -182,7 +388,7 @@ mod tests {
             matches!(
                 {
                     let rest = Restriction::new_inclusion(vec![String::from(".*hell")]);
-                    rest.re_search_type
+                    rest.regexs[0].1
                 },
                 ReSearchType::Inclusive
             ),
@@ -192,7 +398,7 @@ mod tests {
             matches!(
                 {
                     let rest = Restriction::new_exclusion(vec![String::from(".*hell")]);
-                    rest.re_search_type
+                    rest.regexs[0].1
                 },
                 ReSearchType::Exclusive
             ),
@@ -212,7 +418,7 @@ mod tests {
         assert!(
             {
                 let rest = Restriction::new_inclusion(vec![String::from("hell*")]);
-                vec!["hell", "helloe$", "helloa", "hell"]
+                ["hell", "helloe$", "helloa", "hell"]
                     .iter()
                     .all(|e| rest.clone().verify_table_name(e))
             },
@@ -221,7 +427,7 @@ mod tests {
         assert!(
             !{
                 let rest = Restriction::new_inclusion(vec![String::from("*ll*")]);
-                vec!["hel", "heloe$", "heloa", "helel"]
+                ["hel", "heloe$", "heloa", "helel"]
                     .iter()
                     .all(|e| rest.clone().verify_table_name(e))
             },
@@ -231,7 +437,7 @@ mod tests {
             {
                 let rest =
                     Restriction::new_inclusion(vec![String::from("*ll*"), String::from("he*")]);
-                vec!["hey", "heloe$", "heloa", "helell", "llorn"]
+                ["hey", "heloe$", "heloa", "helell", "llorn"]
                     .iter()
                     .all(|e| rest.clone().verify_table_name(e))
             },
@@ -240,7 +446,7 @@ mod tests {
         assert!(
             {
                 let rest = Restriction::new_inclusion(vec![]);
-                vec!["hey", "heloe$", "heloa", "helell", "llorn"]
+                ["hey", "heloe$", "heloa", "helell", "llorn"]
                     .iter()
                     .all(|e| rest.clone().verify_table_name(e))
             },
@@ -267,7 +473,7 @@ mod tests {
         assert!(
             {
                 let rest = Restriction::new_exclusion(vec![String::from("*ll*")]);
-                vec!["hel", "heloe$", "heloa", "helel"]
+                ["hel", "heloe$", "heloa", "helel"]
                     .iter()
                     .all(|e| rest.clone().verify_table_name(e))
             },
@@ -277,7 +483,7 @@ mod tests {
             !{
                 let rest =
                     Restriction::new_exclusion(vec![String::from("*ll*"), String::from("he*")]);
-                vec!["hey", "heloe$", "heloa", "helell", "llorn"]
+                ["hey", "heloe$", "heloa", "helell", "llorn"]
                     .iter()
                     .all(|e| rest.clone().verify_table_name(e))
             },
@@ -286,11 +492,66 @@ mod tests {
         assert!(
             {
                 let rest = Restriction::new_exclusion(vec![]);
-                vec!["hey", "heloe$", "heloa", "helell", "llorn"]
+                ["hey", "heloe$", "heloa", "helell", "llorn"]
                     .iter()
                     .all(|e| rest.clone().verify_table_name(e))
             },
             "No regex"
         );
     }
+
+    #[test]
+    fn test_verify_table_name_combined() {
+        let rest = Restriction::new_inclusion(vec![String::from("user*"), String::from("role")])
+            .with_exclusion(vec![String::from("*_log")]);
+        assert!(
+            ["user", "user_role", "role"]
+                .iter()
+                .all(|e| rest.clone().verify_table_name(e)),
+            "included"
+        );
+        assert!(
+            !["user_log", "role_log", "right"]
+                .iter()
+                .any(|e| rest.clone().verify_table_name(e)),
+            "excluded or not included"
+        );
+        let rest = Restriction::new_inclusion(vec![]).with_exclusion(vec![String::from("a.b")]);
+        assert!(
+            rest.clone().verify_table_name("axb"),
+            "dot matched literally"
+        );
+        assert!(!rest.verify_table_name("a.b"), "dot matched literally");
+    }
+
+    #[test]
+    fn test_verify_column_name() {
+        let rest =
+            Restriction::new_exclusion(vec![String::from("log")]).with_column_exclusion(vec![
+                String::from("*.created_at"),
+                String::from("re:^user\\.pass"),
+            ]);
+        assert!(rest.has_column_exclusions(), "column exclusions");
+        assert!(
+            !rest.verify_column_name("user", "created_at"),
+            "excluded column"
+        );
+        assert!(
+            !rest.verify_column_name("user", "password_hash"),
+            "regular expression"
+        );
+        assert!(
+            rest.verify_column_name("role", "password_hash"),
+            "other table"
+        );
+        assert!(rest.verify_column_name("log", "id"), "excluded table");
+        assert!(
+            rest.clone().verify_table_name("user"),
+            "tables not concerned"
+        );
+        assert!(
+            !Restriction::new_inclusion(vec![]).has_column_exclusions(),
+            "no column exclusion"
+        );
+    }
 }
@@ -0,0 +1,1631 @@
+// Copyright ⓒ 2021-2024 LABEYE Loïc
+// This tool is distributed under the MIT License, check out [here](https://github.com/nag763/doteur/blob/main/LICENCE.MD).
+
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+
+use crate::add_traits::Trim;
+use crate::diagnostic::Diagnostic;
+use crate::errors::DoteurCoreError;
+
+lazy_static! {
+    ///Get the default value of a column, along with its casts such as ::character varying
+    static ref RE_DEFAULT : Regex = Regex::new(r####"(?i)^DEFAULT\s+(?P<default>(?:'(?:[^']|'')*'|\([^\)]*\))(?:::(?:[\w.]|"(?:[^"]|"")*")+(?:\([^)]*\))?(?:\s+(?:varying|precision|with|without|time|zone)\b(?:\([^)]*\))?)*(?:\[\])*)*|[^\s,]*::(?:[\w.]|"(?:[^"]|"")*")+(?:\([^)]*\))?(?:\s+(?:varying|precision|with|without|time|zone)\b(?:\([^)]*\))?)*(?:\[\])*[^\s,]*|[^\s,]+)"####).unwrap();
+    ///Check if the column can't be null
+    static ref RE_NOT_NULL : Regex = Regex::new(r####"(?i)^NOT\s+NULL\b"####).unwrap();
+    ///Check if the column is unique
+    static ref RE_UNIQUE : Regex = Regex::new(r####"(?i)^UNIQUE\b"####).unwrap();
+    ///Keywords ending the type declaration of a column
+    static ref RE_TYPE_END : Regex = Regex::new(r####"(?i)^(?:NOT|NULL|DEFAULT|PRIMARY|UNIQUE|REFERENCES|AUTO_INCREMENT|AUTOINCREMENT|CHECK|COMMENT|GENERATED|CONSTRAINT|COLLATE|ON|IDENTITY|AS)\b"####).unwrap();
+    ///Check if the column values are generated by the database
+    static ref RE_AUTO_INCREMENT : Regex = Regex::new(r####"(?i)^(?:AUTO_INCREMENT|AUTOINCREMENT|IDENTITY)\b"####).unwrap();
+    ///Check if the column is a serial one, which is auto incremented
+    static ref RE_SERIAL : Regex = Regex::new(r####"(?i)^(?:BIG|SMALL)?SERIAL[248]?$"####).unwrap();
+    ///Get the start of the expression of a generated column
+    static ref RE_GENERATED : Regex = Regex::new(r####"(?i)^(?:GENERATED\s+ALWAYS\s+)?AS\s*\("####).unwrap();
+    ///Get the start of the check constraint of a column
+    static ref RE_CHECK : Regex = Regex::new(r####"(?i)^CHECK\s*\("####).unwrap();
+    ///Get the comment of a column
+    static ref RE_COMMENT : Regex = Regex::new(r####"(?i)^COMMENT\s+(?:'(?P<single>(?:[^']|'')*)'|"(?P<double>(?:[^"]|"")*)")"####).unwrap();
+}
+
+/// The referential action executed when a refered row is deleted or updated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferentialAction {
+    /// The refering columns are set to null
+    SetNull,
+    /// The refering rows are deleted or updated as well
+    Cascade,
+    /// The action is rejected
+    Restrict,
+    /// The action is rejected at the end of the statement
+    NoAction,
+    /// The refering columns are set to their default value
+    SetDefault,
+}
+
+impl ReferentialAction {
+    /// Returns the referential action from a str
+    ///
+    /// Unknown inputs are considered as restrictive
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action as written in the SQL input
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doteur_core::schema::ReferentialAction;
+    /// assert_eq!(ReferentialAction::from_str("CASCADE"), ReferentialAction::Cascade);
+    /// assert_eq!(ReferentialAction::from_str("set  null"), ReferentialAction::SetNull);
+    /// assert_eq!(ReferentialAction::from_str("foo"), ReferentialAction::Restrict);
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(action: &str) -> ReferentialAction {
+        let normalized: String = action
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .to_uppercase();
+        match normalized.as_str() {
+            "SET NULL" => ReferentialAction::SetNull,
+            "CASCADE" => ReferentialAction::Cascade,
+            "NO ACTION" => ReferentialAction::NoAction,
+            "SET DEFAULT" => ReferentialAction::SetDefault,
+            _ => ReferentialAction::Restrict,
+        }
+    }
+
+    /// Returns the action as it would be written in SQL
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            ReferentialAction::SetNull => "SET NULL",
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::NoAction => "NO ACTION",
+            ReferentialAction::SetDefault => "SET DEFAULT",
+        }
+    }
+}
+
+/// The column refered by a foreign key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnReference {
+    /// The refered table
+    table: String,
+    /// The refered column
+    column: String,
+}
+
+impl ColumnReference {
+    /// Returns the refered table
+    pub fn get_table(&self) -> &str {
+        &self.table
+    }
+
+    /// Returns the refered column
+    pub fn get_column(&self) -> &str {
+        &self.column
+    }
+}
+
+/// The number of refering rows a refered row can have
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cardinality {
+    /// A refered row is refered by one row at most, as the refering keys are unique
+    OneToOne,
+    /// A refered row can be refered by several rows
+    OneToMany,
+}
+
+impl Cardinality {
+    /// Returns the cardinality as written in the structured outputs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Cardinality::OneToOne => "one_to_one",
+            Cardinality::OneToMany => "one_to_many",
+        }
+    }
+}
+
+/// An index or a unique constraint declared on a table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Index {
+    /// Name of the index if declared
+    name: Option<String>,
+    /// The indexed columns, or expressions, in their declaration order
+    columns: Vec<String>,
+    /// Whether the index is a unique one
+    unique: bool,
+}
+
+impl Index {
+    /// Creates a new index
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the index if declared
+    /// * `columns` - The indexed columns, or expressions
+    /// * `unique` - Whether the index is a unique one
+    pub fn new(name: Option<String>, columns: Vec<String>, unique: bool) -> Index {
+        Index {
+            name,
+            columns,
+            unique,
+        }
+    }
+
+    /// Returns the name of the index if declared
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the indexed columns, or expressions
+    pub fn get_columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns whether the index is a unique one
+    pub fn is_unique(&self) -> bool {
+        self.unique
+    }
+}
+
+/// A check constraint declared on a table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    /// Name of the constraint if declared
+    name: Option<String>,
+    /// The checked expression, without its enclosing parenthesis
+    expression: String,
+}
+
+impl Check {
+    /// Creates a new check constraint
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the constraint if declared
+    /// * `expression` - The checked expression
+    pub fn new(name: Option<String>, expression: &str) -> Check {
+        Check {
+            name,
+            expression: expression.trim_leading_trailing(),
+        }
+    }
+
+    /// Returns the name of the constraint if declared
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the checked expression
+    pub fn get_expression(&self) -> &str {
+        &self.expression
+    }
+}
+
+/// How much of the definition of the columns is shown in the rendered diagrams
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Only the type of the columns is shown
+    Type,
+    /// The type of the columns is shown along whether they accept null values
+    Nullability,
+    /// The whole definition of the columns is shown, along the check constraints of the
+    /// tables
+    #[default]
+    Full,
+}
+
+impl Verbosity {
+    /// Returns the verbosity from a str
+    ///
+    /// Unknown inputs are considered as the full verbosity
+    ///
+    /// # Arguments
+    ///
+    /// * `verbosity` - The name of the verbosity
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doteur_core::schema::Verbosity;
+    /// assert_eq!(Verbosity::from_str("type"), Verbosity::Type);
+    /// assert_eq!(Verbosity::from_str("NULLABILITY"), Verbosity::Nullability);
+    /// assert_eq!(Verbosity::from_str("foo"), Verbosity::Full);
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(verbosity: &str) -> Verbosity {
+        match verbosity.trim().to_lowercase().as_str() {
+            "type" => Verbosity::Type,
+            "nullability" => Verbosity::Nullability,
+            _ => Verbosity::Full,
+        }
+    }
+}
+
+/// A column of a SQL table
+#[derive(Debug, Clone)]
+pub struct Column {
+    /// Name of the column
+    name: String,
+    /// Type of the column as declared
+    data_type: String,
+    /// Whether the column accepts null values
+    nullable: bool,
+    /// The default value of the column if any
+    default: Option<String>,
+    /// Whether the column is part of the primary key
+    primary_key: bool,
+    /// Whether the column is declared as unique
+    unique: bool,
+    /// Whether the values of the column are generated by the database on insertion
+    auto_increment: bool,
+    /// The expression computing the column if it is a generated one
+    generated: Option<String>,
+    /// The expression checked by the column constraint if any
+    check: Option<String>,
+    /// The comment of the column if any
+    comment: Option<String>,
+    /// The refered column if the column is a foreign key
+    foreign_key: Option<ColumnReference>,
+    /// The whole definition following the column name
+    definition: String,
+}
+
+impl Column {
+    /// Creates a new column from its name and its definition
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the column
+    /// * `definition` - What follows the column name in the SQL declaration
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doteur_core::schema::Column;
+    /// let column = Column::new("price", "DECIMAL(10, 2) NOT NULL DEFAULT 0");
+    /// assert_eq!(column.get_data_type(), "DECIMAL(10, 2)");
+    /// assert_eq!(column.get_default(), Some("0"));
+    /// assert!(!column.is_nullable());
+    ///
+    /// let column = Column::new("total", "INT AS (price * quantity) CHECK (total > 0) COMMENT 'In cents'");
+    /// assert_eq!(column.get_generated(), Some("price * quantity"));
+    /// assert_eq!(column.get_check(), Some("total > 0"));
+    /// assert_eq!(column.get_comment(), Some("In cents"));
+    /// ```
+    pub fn new(name: &str, definition: &str) -> Column {
+        let definition: String = definition.trim_leading_trailing();
+        let data_type: String = get_data_type(&definition);
+        Column {
+            name: name.to_string(),
+            auto_increment: RE_SERIAL.is_match(&data_type)
+                || find_keyword(&definition, &RE_AUTO_INCREMENT).is_some(),
+            generated: find_keyword(&definition, &RE_GENERATED)
+                .map(|i| get_parenthesized(&definition[i..])),
+            check: find_keyword(&definition, &RE_CHECK)
+                .map(|i| get_parenthesized(&definition[i..])),
+            comment: find_keyword(&definition, &RE_COMMENT)
+                .and_then(|i| RE_COMMENT.captures(&definition[i..]))
+                .and_then(
+                    |captures| match (captures.name("single"), captures.name("double")) {
+                        (Some(m), _) => Some(m.as_str().replace("''", "'")),
+                        (_, Some(m)) => Some(m.as_str().replace("\"\"", "\"")),
+                        _ => None,
+                    },
+                ),
+            data_type,
+            nullable: find_keyword(&definition, &RE_NOT_NULL).is_none(),
+            default: find_keyword(&definition, &RE_DEFAULT)
+                .and_then(|i| RE_DEFAULT.captures(&definition[i..]))
+                .and_then(|captures| captures.name("default"))
+                // The identity columns are generated by default as identity
+                .filter(|m| !m.as_str().eq_ignore_ascii_case("AS"))
+                .map(|m| m.as_str().to_string()),
+            primary_key: false,
+            unique: find_keyword(&definition, &RE_UNIQUE).is_some(),
+            foreign_key: None,
+            definition,
+        }
+    }
+
+    /// Creates a new column being part of the primary key
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the column
+    /// * `definition` - What follows the column name in the SQL declaration
+    pub fn new_pk(name: &str, definition: &str) -> Column {
+        let mut column: Column = Column::new(name, definition);
+        column.add_pk_nature();
+        column
+    }
+
+    /// Returns the name of the column
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of the column
+    pub fn get_data_type(&self) -> &str {
+        &self.data_type
+    }
+
+    /// Returns whether the column accepts null values
+    pub fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+
+    /// Returns the default value of the column
+    pub fn get_default(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
+    /// Returns whether the column is part of the primary key
+    pub fn is_primary_key(&self) -> bool {
+        self.primary_key
+    }
+
+    /// Returns whether the column is declared as unique
+    pub fn is_unique(&self) -> bool {
+        self.unique
+    }
+
+    /// Returns whether the column is a foreign key
+    pub fn is_foreign_key(&self) -> bool {
+        self.foreign_key.is_some()
+    }
+
+    /// Returns whether the values of the column are generated by the database on insertion
+    ///
+    /// It is the case of the auto incremented, identity and serial columns.
+    pub fn is_auto_increment(&self) -> bool {
+        self.auto_increment
+    }
+
+    /// Returns the expression computing the column if it is a generated one
+    pub fn get_generated(&self) -> Option<&str> {
+        self.generated.as_deref()
+    }
+
+    /// Returns the expression checked by the column constraint if any
+    pub fn get_check(&self) -> Option<&str> {
+        self.check.as_deref()
+    }
+
+    /// Returns the comment of the column if any
+    pub fn get_comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// Returns the column refered by this one if it is a foreign key
+    pub fn get_foreign_key(&self) -> Option<&ColumnReference> {
+        self.foreign_key.as_ref()
+    }
+
+    /// Returns the definition of the column as declared
+    pub fn get_definition(&self) -> &str {
+        &self.definition
+    }
+
+    /// Returns the description of the column shown in the diagrams, with the given verbosity
+    ///
+    /// # Arguments
+    ///
+    /// * `verbosity` - How much of the definition is shown
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doteur_core::schema::{Column, Verbosity};
+    /// let column = Column::new("name", "VARCHAR(255) NOT NULL DEFAULT 'foo'");
+    /// assert_eq!(column.get_description(Verbosity::Type), "VARCHAR(255)");
+    /// assert_eq!(column.get_description(Verbosity::Nullability), "VARCHAR(255) NOT NULL");
+    /// assert_eq!(column.get_description(Verbosity::Full), "VARCHAR(255) NOT NULL DEFAULT 'foo'");
+    ///
+    /// let column = Column::new("name", "VARCHAR(255) COMMENT 'Full name' NOT NULL");
+    /// assert_eq!(column.get_description(Verbosity::Full), "VARCHAR(255) NOT NULL");
+    /// ```
+    pub fn get_description(&self, verbosity: Verbosity) -> String {
+        match (verbosity, self.nullable) {
+            (Verbosity::Type, _) => self.data_type.clone(),
+            (Verbosity::Nullability, true) => format!("{} NULL", self.data_type),
+            (Verbosity::Nullability, false) => format!("{} NOT NULL", self.data_type),
+            // The comment is shown apart from the definition
+            (Verbosity::Full, _) => {
+                match find_keyword(&self.definition, &RE_COMMENT).and_then(|i| {
+                    RE_COMMENT
+                        .find(&self.definition[i..])
+                        .map(|m| (i, i + m.end()))
+                }) {
+                    Some((start, end)) => format!(
+                        "{} {}",
+                        self.definition[..start].trim_end(),
+                        self.definition[end..].trim_start()
+                    )
+                    .trim_leading_trailing(),
+                    None => self.definition.clone(),
+                }
+            }
+        }
+    }
+
+    /// Add PK nature to the column
+    pub(crate) fn add_pk_nature(&mut self) {
+        self.primary_key = true;
+        self.nullable = false;
+    }
+
+    /// Set the default value of the column
+    pub(crate) fn set_default(&mut self, default: String) {
+        self.default = Some(default);
+    }
+
+    /// Set the comment of the column, removing it if none is given
+    pub(crate) fn set_comment(&mut self, comment: Option<String>) {
+        self.comment = comment;
+    }
+
+    /// Add FK nature to the column
+    pub(crate) fn add_fk_nature(&mut self, foreign_table: &str, foreign_key: &str) {
+        if self.foreign_key.is_none() {
+            self.foreign_key = Some(ColumnReference {
+                table: foreign_table.to_string(),
+                column: foreign_key.to_string(),
+            });
+        }
+    }
+}
+
+/// Returns the position of the first keyword matching the given regex in a column definition
+///
+/// Only the keywords found outside of parenthesis and quotes, and following a whitespace, are
+/// considered, so that the content of the expressions and of the strings is ignored.
+///
+/// # Arguments
+///
+/// * `definition` - What follows the column name in the SQL declaration
+/// * `keyword` - Regex matching the start of the keyword
+fn find_keyword(definition: &str, keyword: &Regex) -> Option<usize> {
+    let mut depth: usize = 0;
+    let mut quote: Option<char> = None;
+    let mut previous: char = ' ';
+    for (i, c) in definition.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => (),
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, _)
+                if depth == 0 && previous.is_whitespace() && keyword.is_match(&definition[i..]) =>
+            {
+                return Some(i);
+            }
+            _ => (),
+        }
+        previous = c;
+    }
+    None
+}
+
+/// Returns the content of the first parenthesis of the input, until the one closing it
+///
+/// If the parenthesis is never closed, the whole content following it is returned.
+fn get_parenthesized(input: &str) -> String {
+    let start: usize = match input.find('(') {
+        Some(start) => start + 1,
+        None => return String::new(),
+    };
+    let mut depth: usize = 0;
+    let mut quote: Option<char> = None;
+    for (i, c) in input[start..].char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => (),
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') if depth == 0 => {
+                return input[start..start + i].trim_leading_trailing();
+            }
+            (None, ')') => depth -= 1,
+            _ => (),
+        }
+    }
+    input[start..].trim_leading_trailing()
+}
+
+/// Returns the type from a column definition
+///
+/// The type is what is declared before the first constraint keyword found outside of
+/// parenthesis and quotes.
+fn get_data_type(definition: &str) -> String {
+    match find_keyword(definition, &RE_TYPE_END) {
+        Some(i) => definition[..i].trim_leading_trailing(),
+        None => definition.trim_leading_trailing(),
+    }
+}
+
+/// Trait for retrieving and modifying columns from a vec
+pub(crate) trait KeyValueMap {
+    /// Returns the index of a column
+    ///
+    /// # Arguments
+    ///
+    /// * `attr_name` - Name of the column to be retrieved
+    fn index_of_attribute(&self, attr_name: &str) -> Result<usize, DoteurCoreError>;
+
+    /// Add PK nature to a column in the vec
+    ///
+    /// # Arguments
+    ///
+    /// * `attr_name` - Name of the column to be retrieved
+    fn add_pk_nature_to_attribute(&mut self, attr_name: &str) -> Result<usize, DoteurCoreError>;
+
+    /// Add FK nature to an existing column
+    ///
+    /// # Arguments
+    ///
+    /// * `attr_name` - Name of the column to be retrieved
+    /// * `foreign_table` - Name of the refered table
+    /// * `foreign_key` - Name of the refered key
+    fn add_fk_nature_to_attribute(
+        &mut self,
+        attr_name: &str,
+        foreign_table: &str,
+        foreign_key: &str,
+    ) -> Result<usize, DoteurCoreError>;
+
+    /// Push column or replace it
+    ///
+    /// # `value` - The column to push
+    fn push_or_replace_attribute(&mut self, value: Column);
+}
+
+impl KeyValueMap for Vec<Column> {
+    fn index_of_attribute(&self, attr_name: &str) -> Result<usize, DoteurCoreError> {
+        let index: Option<usize> = self.iter().rposition(|attr| attr.name == attr_name);
+
+        match index {
+            Some(v) => Ok(v),
+            None => Err(DoteurCoreError::logic_error(
+                format!("Attribute {} not found", attr_name).as_str(),
+                file!(),
+                line!(),
+            )),
+        }
+    }
+
+    fn push_or_replace_attribute(&mut self, value: Column) {
+        match self.index_of_attribute(value.name.as_str()) {
+            Ok(index) => {
+                let _ = std::mem::replace(&mut self[index], value);
+            }
+            Err(_) => self.push(value),
+        };
+    }
+
+    fn add_pk_nature_to_attribute(&mut self, attr_name: &str) -> Result<usize, DoteurCoreError> {
+        match self.index_of_attribute(attr_name) {
+            Ok(index) => {
+                self[index].add_pk_nature();
+                Ok(index)
+            }
+            Err(_) => Err(DoteurCoreError::logic_error(
+                format!(
+                    "Can't add pk nature to the {} attribute not present in the vec",
+                    attr_name
+                )
+                .as_str(),
+                file!(),
+                line!(),
+            )),
+        }
+    }
+
+    fn add_fk_nature_to_attribute(
+        &mut self,
+        attr_name: &str,
+        foreign_table: &str,
+        foreign_key: &str,
+    ) -> Result<usize, DoteurCoreError> {
+        match self.index_of_attribute(attr_name) {
+            Ok(index) => {
+                self[index].add_fk_nature(foreign_table, foreign_key);
+                Ok(index)
+            }
+            Err(_) => Err(DoteurCoreError::logic_error(
+                format!(
+                    "Can't add fk nature to the {} attribute not present in the vec",
+                    attr_name
+                )
+                .as_str(),
+                file!(),
+                line!(),
+            )),
+        }
+    }
+}
+
+/// A SQL table and its columns
+#[derive(Debug, Clone)]
+pub struct Table {
+    /// Name of the table, prefixed by its schema when the tables span several schemas
+    name: String,
+    /// The schema the table has been declared in, if any
+    schema: Option<String>,
+    /// Columns of the table, in their declaration order
+    columns: Vec<Column>,
+    /// Indexes and unique constraints of the table, primary key excluded
+    indexes: Vec<Index>,
+    /// Check constraints declared on the table rather than on one of its columns
+    checks: Vec<Check>,
+    /// The comment of the table if any
+    comment: Option<String>,
+}
+
+impl Table {
+    /// Creates a new table without any column
+    pub fn new(name: &str) -> Table {
+        Table {
+            name: name.to_string(),
+            schema: None,
+            columns: Vec::new(),
+            indexes: Vec::new(),
+            checks: Vec::new(),
+            comment: None,
+        }
+    }
+
+    /// Returns the name of the table
+    ///
+    /// When the tables of the schema have been declared in several schemas, the name is
+    /// prefixed by the schema of the table, such as `billing.invoice`.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the schema the table has been declared in, if any
+    pub fn get_schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+
+    /// Returns whether the table is the one refered by the given name
+    ///
+    /// The name can be prefixed by the schema of the table or not.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name refering to a table
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doteur_core::parse_schema;
+    /// let schema = parse_schema("CREATE TABLE billing.invoice (id INT);", None);
+    /// let table = &schema.get_tables()[0];
+    /// assert!(table.is_named("invoice"));
+    /// assert!(table.is_named("billing.invoice"));
+    /// assert!(!table.is_named("sales.invoice"));
+    /// ```
+    pub fn is_named(&self, name: &str) -> bool {
+        let local_name: &str = self.get_local_name();
+        self.name == name
+            || local_name == name
+            || match (self.schema.as_deref(), name.split_once('.')) {
+                (Some(schema), Some(qualified_name)) => qualified_name == (schema, local_name),
+                // The tables declared without schema can belong to any of them
+                (None, Some((_, name))) => local_name == name,
+                _ => false,
+            }
+    }
+
+    /// Returns whether the table is named exactly as the given name, its schema included
+    fn is_exactly_named(&self, name: &str) -> bool {
+        self.name == name
+            || self
+                .schema
+                .as_deref()
+                .is_some_and(|schema| name.split_once('.') == Some((schema, self.get_local_name())))
+    }
+
+    /// Returns the name of the table without its schema
+    fn get_local_name(&self) -> &str {
+        match &self.schema {
+            Some(schema) => self
+                .name
+                .strip_prefix(schema.as_str())
+                .and_then(|name| name.strip_prefix('.'))
+                .unwrap_or(&self.name),
+            None => &self.name,
+        }
+    }
+
+    /// Set the schema the table has been declared in
+    pub(crate) fn set_schema(&mut self, schema: Option<String>) {
+        self.schema = schema;
+    }
+
+    /// Returns the columns of the table
+    pub fn get_columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Returns the column with the given name if it exists
+    pub fn get_column(&self, name: &str) -> Option<&Column> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+
+    /// Returns the columns being part of the primary key
+    pub fn get_primary_key(&self) -> Vec<&Column> {
+        self.columns.iter().filter(|c| c.primary_key).collect()
+    }
+
+    /// Returns the indexes and unique constraints of the table
+    pub fn get_indexes(&self) -> &[Index] {
+        &self.indexes
+    }
+
+    /// Returns the check constraints declared on the table rather than on one of its columns
+    pub fn get_checks(&self) -> &[Check] {
+        &self.checks
+    }
+
+    /// Returns the comment of the table if any
+    pub fn get_comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// Adds a check constraint to the table
+    pub(crate) fn add_check(&mut self, check: Check) {
+        self.checks.push(check);
+    }
+
+    /// Set the comment of the table, removing it if none is given
+    pub(crate) fn set_comment(&mut self, comment: Option<String>) {
+        self.comment = comment;
+    }
+
+    /// Adds an index to the table
+    ///
+    /// A unique index on a single column makes the column unique.
+    pub(crate) fn add_index(&mut self, index: Index) {
+        if let (true, [column_name]) = (index.unique, index.columns.as_slice()) {
+            if let Some(column) = self.get_column_mut(column_name) {
+                column.unique = true;
+            }
+        }
+        self.indexes.push(index);
+    }
+
+    /// Returns whether the given columns identify at most one row of the table
+    ///
+    /// It is the case when they include the primary key, a unique column, or the columns of
+    /// a unique index.
+    pub fn is_unique_key(&self, columns: &[&str]) -> bool {
+        let primary_key: Vec<&Column> = self.get_primary_key();
+        (!primary_key.is_empty()
+            && primary_key
+                .iter()
+                .all(|column| columns.contains(&column.name.as_str())))
+            || self
+                .columns
+                .iter()
+                .any(|column| column.unique && columns.contains(&column.name.as_str()))
+            || self.indexes.iter().any(|index| {
+                index.unique
+                    && index
+                        .columns
+                        .iter()
+                        .all(|column| columns.contains(&column.as_str()))
+            })
+    }
+
+    /// Returns the column with the given name as mutable if it exists
+    pub(crate) fn get_column_mut(&mut self, name: &str) -> Option<&mut Column> {
+        self.columns.iter_mut().find(|column| column.name == name)
+    }
+
+    /// Adds a column to the table
+    pub(crate) fn add_column(&mut self, column: Column) {
+        self.columns.push(column);
+    }
+
+    /// Adds a column part of the primary key to the table, replacing the former one if
+    /// already declared
+    pub(crate) fn add_column_pk(&mut self, column: Column) {
+        self.columns.push_or_replace_attribute(column);
+    }
+
+    /// Adds primary key nature to given column
+    pub(crate) fn add_pk_nature_to_column(&mut self, key: &str) -> Result<usize, DoteurCoreError> {
+        self.columns.add_pk_nature_to_attribute(key)
+    }
+
+    /// Adds foreign key nature to given column
+    pub(crate) fn add_fk_nature_to_column(
+        &mut self,
+        key: &str,
+        fk_table: &str,
+        fk_col: &str,
+    ) -> Result<usize, DoteurCoreError> {
+        self.columns
+            .add_fk_nature_to_attribute(key, fk_table, fk_col)
+    }
+}
+
+/// A relation is a link between a list of pair of keys
+/// between two tables
+#[derive(Debug, Clone)]
+pub struct Relation {
+    /// Table that got the refering key
+    origin_table: String,
+    /// Table that got the refered key
+    refered_table: String,
+    /// List of keys refering each others
+    keys: Vec<(String, String)>,
+    /// Action executed on delete, if declared
+    on_delete: Option<ReferentialAction>,
+    /// Action executed on update, if declared
+    on_update: Option<ReferentialAction>,
+    /// Number of refering rows a refered row can have
+    cardinality: Cardinality,
+    /// Whether a refering row can exist without a refered one
+    optional: bool,
+}
+
+impl Relation {
+    /// A new relation
+    pub(crate) fn new(
+        origin_table: String,
+        refered_table: String,
+        on_delete: Option<ReferentialAction>,
+        on_update: Option<ReferentialAction>,
+    ) -> Relation {
+        Relation {
+            origin_table,
+            refered_table,
+            keys: Vec::new(),
+            on_delete,
+            on_update,
+            cardinality: Cardinality::OneToMany,
+            optional: false,
+        }
+    }
+
+    /// A new relation with a single pair
+    pub(crate) fn new_with_single_pair(
+        origin_table: String,
+        refered_table: String,
+        origin_key: String,
+        refered_key: String,
+        on_delete: Option<ReferentialAction>,
+        on_update: Option<ReferentialAction>,
+    ) -> Relation {
+        Relation {
+            origin_table,
+            refered_table,
+            keys: vec![(origin_key, refered_key)],
+            on_delete,
+            on_update,
+            cardinality: Cardinality::OneToMany,
+            optional: false,
+        }
+    }
+
+    /// Returns the table that got the refering key
+    pub fn get_origin_table(&self) -> &str {
+        self.origin_table.as_str()
+    }
+
+    /// Returns the refered table
+    pub fn get_refered_table(&self) -> &str {
+        self.refered_table.as_str()
+    }
+
+    /// Returns the pairs of keys, the refering one first and the refered one second
+    pub fn get_pairs_of_keys(&self) -> &[(String, String)] {
+        &self.keys
+    }
+
+    /// Returns the number of pairs of keys
+    pub fn get_number_of_pairs_of_keys(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns the action executed on delete, if declared
+    ///
+    /// When none is declared, the database applies its default one, which is no action.
+    pub fn get_on_delete(&self) -> Option<ReferentialAction> {
+        self.on_delete
+    }
+
+    /// Returns the action executed on update, if declared
+    ///
+    /// When none is declared, the database applies its default one, which is no action.
+    pub fn get_on_update(&self) -> Option<ReferentialAction> {
+        self.on_update
+    }
+
+    /// Returns the number of refering rows a refered row can have
+    ///
+    /// The relation is a one to one when the refering keys are the primary key of their
+    /// table, or are covered by a unique constraint.
+    pub fn get_cardinality(&self) -> Cardinality {
+        self.cardinality
+    }
+
+    /// Returns whether a refering row can exist without a refered one, which is the case
+    /// when one of the refering keys is nullable
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// Returns the hint shown along the relation about the action executed on update, if one
+    /// is declared
+    pub(crate) fn get_on_update_hint(&self) -> Option<String> {
+        self.on_update
+            .map(|action| format!("on update {}", action.as_sql().to_lowercase()))
+    }
+
+    /// Add a new pair of key to the relation
+    pub(crate) fn push_pair_of_keys(&mut self, origin_key: String, refered_key: String) {
+        self.keys.push((origin_key, refered_key));
+    }
+}
+
+/// A SQL view, its columns and the tables or views it selects from
+#[derive(Debug, Clone)]
+pub struct View {
+    /// Name of the view
+    name: String,
+    /// Names of the columns of the view, in their declaration order
+    columns: Vec<String>,
+    /// Tables or views the view selects from
+    dependencies: Vec<String>,
+}
+
+impl View {
+    /// Creates a new view
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the view
+    /// * `columns` - Names of the columns of the view
+    /// * `dependencies` - Tables or views the view selects from
+    pub fn new(name: &str, columns: Vec<String>, dependencies: Vec<String>) -> View {
+        View {
+            name: name.to_string(),
+            columns,
+            dependencies,
+        }
+    }
+
+    /// Returns the name of the view
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the names of the columns of the view
+    pub fn get_columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns the tables or views the view selects from
+    pub fn get_dependencies(&self) -> &[String] {
+        &self.dependencies
+    }
+}
+
+/// A schema is the result of the parsing of a SQL input
+///
+/// It holds the tables, the views and the relations between the tables, and can be rendered
+/// afterwards in the format of your choice.
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    /// The tables of the schema
+    tables: Vec<Table>,
+    /// The positions of the tables in the schema, by their name without schema
+    table_index: HashMap<String, Vec<usize>>,
+    /// The views of the schema
+    views: Vec<View>,
+    /// The relations between the tables
+    relations: Vec<Relation>,
+    /// The tables refered by the relations but not part of the schema, rendered as stubs
+    stubs: Vec<String>,
+    /// The parts of the input that couldn't be understood
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Schema {
+    /// Creates an empty schema
+    pub fn new() -> Schema {
+        Schema::default()
+    }
+
+    /// Returns the tables of the schema
+    pub fn get_tables(&self) -> &[Table] {
+        &self.tables
+    }
+
+    /// Returns the table with the given name if it exists
+    ///
+    /// The name can be prefixed by the schema of the table or not. When several tables match
+    /// it, the one named exactly as given is preferred.
+    pub fn get_table(&self, name: &str) -> Option<&Table> {
+        self.find_table(name, None).map(|index| &self.tables[index])
+    }
+
+    /// Returns the schemas the tables have been declared in, in their declaration order
+    pub fn get_schema_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for schema in self.tables.iter().filter_map(|table| table.get_schema()) {
+            if !names.contains(&schema) {
+                names.push(schema);
+            }
+        }
+        names
+    }
+
+    /// Returns the views of the schema
+    pub fn get_views(&self) -> &[View] {
+        &self.views
+    }
+
+    /// Returns the view with the given name if it exists
+    pub fn get_view(&self, name: &str) -> Option<&View> {
+        self.views.iter().find(|view| view.name == name)
+    }
+
+    /// Returns the relations of the schema
+    pub fn get_relations(&self) -> &[Relation] {
+        &self.relations
+    }
+
+    /// Returns the tables refered by the relations but not part of the schema, such as the
+    /// ones excluded by the restrictions, when they are rendered as stubs
+    pub fn get_stubs(&self) -> &[String] {
+        &self.stubs
+    }
+
+    /// Returns the table with the given name as mutable if it exists
+    pub(crate) fn get_table_mut(&mut self, name: &str) -> Option<&mut Table> {
+        self.find_table(name, None)
+            .map(move |index| &mut self.tables[index])
+    }
+
+    /// Adds a table to the schema
+    pub(crate) fn add_table(&mut self, table: Table) {
+        self.table_index
+            .entry(table.get_local_name().to_string())
+            .or_default()
+            .push(self.tables.len());
+        self.tables.push(table);
+    }
+
+    /// Adds a view to the schema
+    pub(crate) fn add_view(&mut self, view: View) {
+        self.views.push(view);
+    }
+
+    /// Adds a relation to the schema
+    pub(crate) fn add_relation(&mut self, relation: Relation) {
+        self.relations.push(relation);
+    }
+
+    /// Returns the index of the table refered by the given name
+    ///
+    /// When several tables match the name, the one named exactly as given is preferred, then
+    /// the one declared in the given schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name refering to a table, prefixed by its schema or not
+    /// * `schema` - The schema of the refering table, if any
+    fn find_table(&self, name: &str, schema: Option<&str>) -> Option<usize> {
+        let candidates: Vec<usize> = self
+            .get_candidates(name)
+            .into_iter()
+            .filter(|&index| self.tables[index].is_named(name))
+            .collect();
+        candidates
+            .iter()
+            .find(|&&index| self.tables[index].is_exactly_named(name))
+            .or_else(|| {
+                candidates
+                    .iter()
+                    .find(|&&index| schema.is_some() && self.tables[index].get_schema() == schema)
+            })
+            .or_else(|| candidates.first())
+            .copied()
+    }
+
+    /// Returns the index of the table whose name, as returned by [`Table::get_name`], is the
+    /// given one
+    fn find_table_named(&self, name: &str) -> Option<usize> {
+        self.get_candidates(name)
+            .into_iter()
+            .find(|&index| self.tables[index].name == name)
+    }
+
+    /// Returns the indexes of the tables that can be refered by the given name, in their
+    /// declaration order
+    ///
+    /// Only the tables whose name without schema is the given name, with or without its
+    /// prefix, can be refered by it.
+    fn get_candidates(&self, name: &str) -> Vec<usize> {
+        let mut candidates: Vec<usize> = [Some(name), name.split_once('.').map(|(_, name)| name)]
+            .iter()
+            .flatten()
+            .filter_map(|name| self.table_index.get(*name))
+            .flatten()
+            .copied()
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates
+    }
+
+    /// Returns the name of the table refered by the given name, as returned by
+    /// [`Table::get_name`]
+    ///
+    /// When the table isn't part of the schema, its name is only kept prefixed by its schema
+    /// if the tables span several schemas, as the name of the declared tables.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name refering to a table, prefixed by its schema or not
+    /// * `schema` - The schema of the refering table, if any
+    /// * `schema_names` - The schemas the tables have been declared in
+    fn resolve_table_name(
+        &self,
+        name: &str,
+        schema: Option<&str>,
+        schema_names: &[String],
+    ) -> Option<String> {
+        self.find_table(name, schema)
+            .map(|index| self.tables[index].name.clone())
+            .or_else(|| {
+                let (table_schema, local_name) = name.split_once('.')?;
+                match schema_names {
+                    [] => Some(local_name.to_string()),
+                    [single] if single == table_schema => Some(local_name.to_string()),
+                    _ => None,
+                }
+            })
+    }
+
+    /// Identifies the tables by their schema when they span several ones, and resolves the
+    /// tables refered by the relations, the foreign keys and the views
+    ///
+    /// The references whose table isn't part of the schema are only kept prefixed by their
+    /// schema if the tables span several ones.
+    pub(crate) fn resolve_table_names(&mut self) {
+        if self.get_schema_names().len() > 1 {
+            for table in self.tables.iter_mut() {
+                if let Some(schema) = &table.schema {
+                    if table.get_local_name() == table.name {
+                        table.name = format!("{}.{}", schema, table.name);
+                    }
+                }
+            }
+        }
+        let schema_names: Vec<String> = self
+            .get_schema_names()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let schemas: HashMap<&str, Option<&str>> = self
+            .tables
+            .iter()
+            .map(|table| (table.name.as_str(), table.get_schema()))
+            .collect();
+        let relations: Vec<(Option<String>, Option<String>)> = self
+            .relations
+            .iter()
+            .map(|relation| {
+                let origin: Option<String> =
+                    self.resolve_table_name(&relation.origin_table, None, &schema_names);
+                let schema: Option<&str> = origin
+                    .as_deref()
+                    .and_then(|origin| schemas.get(origin).copied().flatten());
+                let refered: Option<String> =
+                    self.resolve_table_name(&relation.refered_table, schema, &schema_names);
+                (origin, refered)
+            })
+            .collect();
+        for (relation, (origin, refered)) in self.relations.iter_mut().zip(relations) {
+            if let Some(origin) = origin {
+                relation.origin_table = origin;
+            }
+            if let Some(refered) = refered {
+                relation.refered_table = refered;
+            }
+        }
+        let references: Vec<Vec<Option<String>>> = self
+            .tables
+            .iter()
+            .map(|table| {
+                table
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        column.foreign_key.as_ref().and_then(|reference| {
+                            self.resolve_table_name(
+                                &reference.table,
+                                table.get_schema(),
+                                &schema_names,
+                            )
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        for (table, references) in self.tables.iter_mut().zip(references) {
+            for (column, reference) in table.columns.iter_mut().zip(references) {
+                if let (Some(foreign_key), Some(reference)) =
+                    (column.foreign_key.as_mut(), reference)
+                {
+                    foreign_key.table = reference;
+                }
+            }
+        }
+        // The views are identified without their schema
+        let dependencies: Vec<Vec<Option<String>>> = self
+            .views
+            .iter()
+            .map(|view| {
+                view.dependencies
+                    .iter()
+                    .map(|dependency| {
+                        self.resolve_table_name(dependency, None, &schema_names)
+                            .or_else(|| {
+                                let (_, name) = dependency.split_once('.')?;
+                                self.get_view(name).map(|view| view.name.clone())
+                            })
+                    })
+                    .collect()
+            })
+            .collect();
+        for (view, resolved) in self.views.iter_mut().zip(dependencies) {
+            for (dependency, resolved) in view.dependencies.iter_mut().zip(resolved) {
+                if let Some(resolved) = resolved {
+                    *dependency = resolved;
+                }
+            }
+        }
+    }
+
+    /// Only keeps the columns matching the given predicate, the relations whose refering keys
+    /// are removed being removed as well
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - Whether a column is kept, given the unqualified name of its table and its name
+    pub(crate) fn retain_columns<F: Fn(&str, &str) -> bool>(&mut self, predicate: F) {
+        let mut removed: HashSet<(String, String)> = HashSet::new();
+        for table in self.tables.iter_mut() {
+            let local_name: String = table.get_local_name().to_string();
+            let table_name: &str = table.name.as_str();
+            table.columns.retain(|column| {
+                let kept: bool = predicate(&local_name, &column.name);
+                if !kept {
+                    removed.insert((table_name.to_string(), column.name.clone()));
+                }
+                kept
+            });
+        }
+        self.relations.retain(|relation| {
+            !relation.keys.iter().any(|(origin_key, _)| {
+                removed.contains(&(relation.origin_table.clone(), origin_key.clone()))
+            })
+        });
+    }
+
+    /// Completes the relations whose refered keys haven't been declared
+    ///
+    /// A foreign key declared without its refered columns refers to the primary key of the
+    /// refered table, which is only known once every table has been parsed.
+    pub(crate) fn resolve_implicit_keys(&mut self) {
+        let mut relations: Vec<Relation> = std::mem::take(&mut self.relations);
+        for relation in relations.iter_mut() {
+            let Relation {
+                origin_table,
+                refered_table,
+                keys,
+                ..
+            } = relation;
+            let primary_key: Vec<String> = match self.find_table_named(refered_table) {
+                Some(index) => self.tables[index]
+                    .get_primary_key()
+                    .iter()
+                    .map(|column| column.name.clone())
+                    .collect(),
+                None => continue,
+            };
+            if primary_key.len() != keys.len() {
+                continue;
+            }
+            for ((origin_key, refered_key), key) in keys.iter_mut().zip(primary_key) {
+                if !refered_key.is_empty() {
+                    continue;
+                }
+                if let Some(reference) = self
+                    .find_table_named(origin_table)
+                    .and_then(|index| self.tables[index].get_column_mut(origin_key))
+                    .and_then(|column| column.foreign_key.as_mut())
+                    .filter(|reference| reference.column.is_empty())
+                {
+                    reference.column = key.clone();
+                }
+                *refered_key = key;
+            }
+        }
+        self.relations = relations;
+    }
+
+    /// Infers the cardinality and the optionality of the relations from the constraints of
+    /// the refering tables
+    ///
+    /// The relations whose refering table isn't part of the schema are left as they are.
+    pub(crate) fn infer_cardinalities(&mut self) {
+        let mut relations: Vec<Relation> = std::mem::take(&mut self.relations);
+        for relation in relations.iter_mut() {
+            let table: &Table = match self.find_table_named(&relation.origin_table) {
+                Some(index) => &self.tables[index],
+                None => continue,
+            };
+            let keys: Vec<&str> = relation
+                .keys
+                .iter()
+                .map(|(origin_key, _)| origin_key.as_str())
+                .collect();
+            relation.cardinality = match table.is_unique_key(&keys) {
+                true => Cardinality::OneToOne,
+                false => Cardinality::OneToMany,
+            };
+            relation.optional = keys.iter().any(|key| {
+                table
+                    .get_column(key)
+                    .is_some_and(|column| column.is_nullable())
+            });
+        }
+        self.relations = relations;
+    }
+
+    /// Only keeps the tables that are within the given number of relations of the focused one,
+    /// whatever the direction of the relations
+    ///
+    /// The relations of the kept tables toward tables that aren't part of the schema and the
+    /// views only selecting from the kept tables are kept as well. Nothing is kept if the
+    /// focused table isn't part of the schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `focus` - The name of the focused table, prefixed by its schema or not
+    /// * `depth` - The maximal number of relations between the focused table and the kept ones
+    /// * `stubs` - Whether the relations of the kept tables toward the other ones are kept
+    pub(crate) fn retain_neighbourhood(&mut self, focus: &str, depth: usize, stubs: bool) {
+        let names: HashSet<String> = self.tables.iter().map(|table| table.name.clone()).collect();
+        let mut kept: HashSet<String> = self
+            .find_table(focus, None)
+            .map(|index| self.tables[index].name.clone())
+            .into_iter()
+            .collect();
+        let mut frontier: HashSet<String> = kept.clone();
+        for _ in 0..depth {
+            let mut next: HashSet<String> = HashSet::new();
+            for relation in self.relations.iter() {
+                let (origin, refered) = (&relation.origin_table, &relation.refered_table);
+                for (from, to) in [(origin, refered), (refered, origin)] {
+                    if frontier.contains(from) && !kept.contains(to) && names.contains(to) {
+                        next.insert(to.clone());
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            kept.extend(next.iter().cloned());
+            frontier = next;
+        }
+        self.tables.retain(|table| kept.contains(&table.name));
+        self.table_index.clear();
+        for (index, table) in self.tables.iter().enumerate() {
+            self.table_index
+                .entry(table.get_local_name().to_string())
+                .or_default()
+                .push(index);
+        }
+        self.relations.retain(|relation| {
+            kept.contains(&relation.origin_table)
+                && (stubs
+                    || kept.contains(&relation.refered_table)
+                    || !names.contains(&relation.refered_table))
+        });
+        self.views.retain(|view| {
+            !view.dependencies.is_empty()
+                && view
+                    .dependencies
+                    .iter()
+                    .all(|dependency| kept.contains(dependency))
+        });
+    }
+
+    /// Collects the tables refered by the relations but not part of the schema as stubs
+    pub(crate) fn collect_stubs(&mut self) {
+        let names: HashSet<&str> = self
+            .tables
+            .iter()
+            .map(|table| table.name.as_str())
+            .collect();
+        let mut stubs: HashSet<String> = self.stubs.iter().cloned().collect();
+        for relation in self.relations.iter() {
+            if !names.contains(relation.refered_table.as_str())
+                && stubs.insert(relation.refered_table.clone())
+            {
+                self.stubs.push(relation.refered_table.clone());
+            }
+        }
+    }
+
+    /// Returns the parts of the input that couldn't be understood while parsing the schema
+    pub fn get_diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Adds a diagnostic to the schema
+    pub(crate) fn add_diagnostic(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_get_data_type() {
+        assert_eq!(get_data_type("INT"), "INT", "single type");
+        assert_eq!(
+            get_data_type("varchar(15) NOT NULL"),
+            "varchar(15)",
+            "not null"
+        );
+        assert_eq!(
+            get_data_type("INT(10) UNSIGNED NOT NULL AUTO_INCREMENT"),
+            "INT(10) UNSIGNED",
+            "unsigned"
+        );
+        assert_eq!(
+            get_data_type("character varying(255) DEFAULT 'x'::character varying"),
+            "character varying(255)",
+            "several words"
+        );
+        assert_eq!(
+            get_data_type("ENUM('NULL', 'DEFAULT') NOT NULL"),
+            "ENUM('NULL', 'DEFAULT')",
+            "keywords in parenthesis"
+        );
+        assert_eq!(get_data_type(""), "", "empty");
+    }
+
+    #[test]
+    fn test_new_column() {
+        let column: Column = Column::new("foo", "varchar(10) NOT NULL DEFAULT 'bar' UNIQUE");
+        assert_eq!(column.get_data_type(), "varchar(10)");
+        assert!(!column.is_nullable());
+        assert_eq!(column.get_default(), Some("'bar'"));
+        assert!(column.is_unique());
+        assert!(!column.is_primary_key());
+
+        let column: Column = Column::new(
+            "foo",
+            "character varying(20) DEFAULT 'bar'::character varying NOT NULL",
+        );
+        assert_eq!(column.get_default(), Some("'bar'::character varying"));
+        assert!(!column.is_nullable());
+
+        let column: Column = Column::new("foo", "TEXT");
+        assert!(column.is_nullable());
+        assert_eq!(column.get_default(), None);
+        assert!(!column.is_unique());
+
+        let column: Column = Column::new_pk("foo", "INT");
+        assert!(column.is_primary_key());
+        assert!(!column.is_nullable());
+    }
+
+    #[test]
+    fn test_column_metadata() {
+        let column: Column = Column::new("id", "INT(10) UNSIGNED NOT NULL AUTO_INCREMENT");
+        assert!(column.is_auto_increment(), "auto increment");
+        assert_eq!(column.get_generated(), None, "not generated");
+
+        let column: Column = Column::new("id", "bigserial");
+        assert!(column.is_auto_increment(), "serial");
+
+        let column: Column = Column::new("id", "integer GENERATED BY DEFAULT AS IDENTITY");
+        assert!(column.is_auto_increment(), "identity");
+        assert_eq!(column.get_generated(), None, "identity isn't generated");
+
+        let column: Column = Column::new(
+            "total",
+            "numeric GENERATED ALWAYS AS (price * (1 + tax)) STORED",
+        );
+        assert_eq!(
+            column.get_generated(),
+            Some("price * (1 + tax)"),
+            "generated always"
+        );
+        assert!(
+            !column.is_auto_increment(),
+            "generated isn't auto increment"
+        );
+
+        let column: Column = Column::new(
+            "status",
+            "VARCHAR(10) DEFAULT 'CHECK (x)' CHECK (status IN ('a', ')')) COMMENT 'It''s the status'",
+        );
+        assert_eq!(
+            column.get_check(),
+            Some("status IN ('a', ')')"),
+            "check outside of the default"
+        );
+        assert_eq!(
+            column.get_comment(),
+            Some("It's the status"),
+            "escaped comment"
+        );
+        assert_eq!(
+            column.get_description(Verbosity::Nullability),
+            "VARCHAR(10) NULL",
+            "nullability"
+        );
+
+        let column: Column = Column::new(
+            "code",
+            "VARCHAR(10) COMMENT 'must be unique, NOT NULL in prod'",
+        );
+        assert!(column.is_nullable(), "not null within the comment");
+        assert!(!column.is_unique(), "unique within the comment");
+
+        let column: Column = Column::new("code", "VARCHAR(10) COMMENT 'default foo'");
+        assert_eq!(column.get_default(), None, "default within the comment");
+
+        let column: Column =
+            Column::new("x", "INT CHECK (x IS NOT NULL OR y = 'DEFAULT z') UNIQUE");
+        assert!(column.is_nullable(), "not null within the check");
+        assert_eq!(column.get_default(), None, "default within the check");
+        assert!(column.is_unique(), "unique after the check");
+
+        let column: Column = Column::new("x", "INT DEFAULT (1 + 2) NOT NULL");
+        assert_eq!(column.get_default(), Some("(1 + 2)"), "default expression");
+        assert!(!column.is_nullable(), "not null after the default");
+    }
+
+    #[test]
+    fn test_referential_action_from_str() {
+        assert_eq!(
+            ReferentialAction::from_str("SET NULL"),
+            ReferentialAction::SetNull
+        );
+        assert_eq!(
+            ReferentialAction::from_str("no\taction"),
+            ReferentialAction::NoAction
+        );
+        assert_eq!(
+            ReferentialAction::from_str("set default"),
+            ReferentialAction::SetDefault
+        );
+        assert_eq!(
+            ReferentialAction::from_str("RESTRICT"),
+            ReferentialAction::Restrict
+        );
+    }
+}
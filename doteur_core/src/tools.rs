@@ -37,11 +37,9 @@ pub fn detect_comas(content: &str) -> Result<Vec<usize>, &str> {
     let mut buffer: String = String::new();
     for (i, c) in content.chars().enumerate() {
         match c {
-            '(' => {
-                // If the parenthesis aren't inside a string
-                if buffer.is_empty() || !self_closables.contains(&buffer.get_last_char()) {
-                    buffer.push(c);
-                }
+            // If the parenthesis aren't inside a string
+            '(' if buffer.is_empty() || !self_closables.contains(&buffer.get_last_char()) => {
+                buffer.push(c);
             }
             ')' => {
                 if !buffer.is_empty() {
@@ -56,11 +54,9 @@ pub fn detect_comas(content: &str) -> Result<Vec<usize>, &str> {
                     return Err("Parenthesis closed without being opened");
                 }
             }
-            '[' => {
-                // If the parenthesis aren't inside a string
-                if buffer.is_empty() || !self_closables.contains(&buffer.get_last_char()) {
-                    buffer.push(c);
-                }
+            // If the hooks aren't inside a string
+            '[' if buffer.is_empty() || !self_closables.contains(&buffer.get_last_char()) => {
+                buffer.push(c);
             }
             ']' => {
                 if !buffer.is_empty() {
@@ -105,10 +101,8 @@ pub fn detect_comas(content: &str) -> Result<Vec<usize>, &str> {
                     buffer.push(c)
                 }
             }
-            ',' => {
-                if buffer.is_empty() {
-                    indexes.push(i);
-                }
+            ',' if buffer.is_empty() => {
+                indexes.push(i);
             }
             _ => (),
         }
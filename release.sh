@@ -1,8 +1,8 @@
 #!bin/sh
 
-numberOfFeatures=3
-featuresToBuild=('--no-default-features' '--features mysql_addons' '--features sqlite_addons' '--all-features')
-packagesNames=('doteur_light' 'doteur_mysql' 'doteur_sqlite' 'doteur')
+numberOfFeatures=4
+featuresToBuild=('--no-default-features' '--features mysql_addons' '--features sqlite_addons' '--features postgres_addons' '--all-features')
+packagesNames=('doteur_light' 'doteur_mysql' 'doteur_sqlite' 'doteur_postgres' 'doteur')
 target_names=('x86_64-unknown-linux-gnu' 'x86_64-pc-windows-gnu')
 
 if ! command -v rustup &> /dev/null
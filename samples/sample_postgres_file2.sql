@@ -0,0 +1,331 @@
+--
+-- PostgreSQL database dump
+--
+
+-- Dumped from database version 15.6
+-- Dumped by pg_dump version 15.6
+
+SET statement_timeout = 0;
+SET lock_timeout = 0;
+SET idle_in_transaction_session_timeout = 0;
+SET client_encoding = 'UTF8';
+SET standard_conforming_strings = on;
+SELECT pg_catalog.set_config('search_path', '', false);
+SET check_function_bodies = false;
+SET xmloption = content;
+SET client_min_messages = warning;
+SET row_security = off;
+
+--
+-- Name: pgcrypto; Type: EXTENSION; Schema: -; Owner: -
+--
+
+CREATE EXTENSION IF NOT EXISTS pgcrypto WITH SCHEMA public;
+
+
+--
+-- Name: EXTENSION pgcrypto; Type: COMMENT; Schema: -; Owner:
+--
+
+COMMENT ON EXTENSION pgcrypto IS 'cryptographic functions';
+
+
+--
+-- Name: OrderStatus; Type: TYPE; Schema: public; Owner: shop
+--
+
+CREATE TYPE public."OrderStatus" AS ENUM (
+    'pending',
+    'paid',
+    'shipped',
+    'cancelled'
+);
+
+
+ALTER TYPE public."OrderStatus" OWNER TO shop;
+
+--
+-- Name: touch_updated_at(); Type: FUNCTION; Schema: public; Owner: shop
+--
+
+CREATE FUNCTION public.touch_updated_at() RETURNS trigger
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    NEW."updatedAt" := now();
+    RETURN NEW;
+END;
+$$;
+
+
+ALTER FUNCTION public.touch_updated_at() OWNER TO shop;
+
+SET default_tablespace = '';
+
+SET default_table_access_method = heap;
+
+--
+-- Name: UserAccount; Type: TABLE; Schema: public; Owner: shop
+--
+
+CREATE TABLE public."UserAccount" (
+    "userId" integer NOT NULL,
+    email character varying(320) NOT NULL,
+    "displayName" text,
+    "createdAt" timestamp with time zone DEFAULT now() NOT NULL,
+    "updatedAt" timestamp with time zone
+);
+
+
+ALTER TABLE public."UserAccount" OWNER TO shop;
+
+--
+-- Name: UserAccount_userId_seq; Type: SEQUENCE; Schema: public; Owner: shop
+--
+
+CREATE SEQUENCE public."UserAccount_userId_seq"
+    AS integer
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+ALTER TABLE public."UserAccount_userId_seq" OWNER TO shop;
+
+--
+-- Name: UserAccount_userId_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: shop
+--
+
+ALTER SEQUENCE public."UserAccount_userId_seq" OWNED BY public."UserAccount"."userId";
+
+
+--
+-- Name: address; Type: TABLE; Schema: public; Owner: shop
+--
+
+CREATE TABLE public.address (
+    id bigint NOT NULL,
+    "userId" integer NOT NULL,
+    line1 text NOT NULL,
+    city text NOT NULL,
+    country character(2) DEFAULT 'FR'::bpchar NOT NULL
+);
+
+
+ALTER TABLE public.address OWNER TO shop;
+
+--
+-- Name: address_id_seq; Type: SEQUENCE; Schema: public; Owner: shop
+--
+
+ALTER TABLE public.address ALTER COLUMN id ADD GENERATED ALWAYS AS IDENTITY (
+    SEQUENCE NAME public.address_id_seq
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1
+);
+
+
+--
+-- Name: product; Type: TABLE; Schema: public; Owner: shop
+--
+
+CREATE TABLE public.product (
+    id integer GENERATED BY DEFAULT AS IDENTITY NOT NULL,
+    sku uuid DEFAULT gen_random_uuid() NOT NULL,
+    label character varying(255) NOT NULL,
+    price numeric(10,2) DEFAULT 0.00 NOT NULL,
+    tags text[] DEFAULT '{}'::text[]
+);
+
+
+ALTER TABLE public.product OWNER TO shop;
+
+--
+-- Name: digital_product; Type: TABLE; Schema: public; Owner: shop
+--
+
+CREATE TABLE public.digital_product (
+    "downloadUrl" text NOT NULL
+)
+INHERITS (public.product);
+
+
+ALTER TABLE public.digital_product OWNER TO shop;
+
+--
+-- Name: shop_order; Type: TABLE; Schema: public; Owner: shop
+--
+
+CREATE TABLE public.shop_order (
+    id integer NOT NULL,
+    "userId" integer NOT NULL,
+    "shippingAddressId" bigint,
+    status public."OrderStatus" DEFAULT 'pending'::public."OrderStatus" NOT NULL,
+    "placedAt" timestamp without time zone DEFAULT CURRENT_TIMESTAMP NOT NULL
+)
+WITH (fillfactor='90');
+
+
+ALTER TABLE public.shop_order OWNER TO shop;
+
+--
+-- Name: shop_order_id_seq; Type: SEQUENCE; Schema: public; Owner: shop
+--
+
+CREATE SEQUENCE public.shop_order_id_seq
+    AS integer
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+ALTER TABLE public.shop_order_id_seq OWNER TO shop;
+
+--
+-- Name: shop_order_id_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: shop
+--
+
+ALTER SEQUENCE public.shop_order_id_seq OWNED BY public.shop_order.id;
+
+
+--
+-- Name: order_line; Type: TABLE; Schema: public; Owner: shop
+--
+
+CREATE TABLE public.order_line (
+    "orderId" integer NOT NULL,
+    "productId" integer NOT NULL,
+    quantity smallint DEFAULT 1 NOT NULL,
+    CONSTRAINT order_line_quantity_check CHECK ((quantity > 0))
+);
+
+
+ALTER TABLE public.order_line OWNER TO shop;
+
+--
+-- Name: UserAccount userId; Type: DEFAULT; Schema: public; Owner: shop
+--
+
+ALTER TABLE ONLY public."UserAccount" ALTER COLUMN "userId" SET DEFAULT nextval('public."UserAccount_userId_seq"'::regclass);
+
+
+--
+-- Name: shop_order id; Type: DEFAULT; Schema: public; Owner: shop
+--
+
+ALTER TABLE ONLY public.shop_order ALTER COLUMN id SET DEFAULT nextval('public.shop_order_id_seq'::regclass);
+
+
+--
+-- Name: UserAccount UserAccount_email_key; Type: CONSTRAINT; Schema: public; Owner: shop
+--
+
+ALTER TABLE ONLY public."UserAccount"
+    ADD CONSTRAINT "UserAccount_email_key" UNIQUE (email);
+
+
+--
+-- Name: UserAccount UserAccount_pkey; Type: CONSTRAINT; Schema: public; Owner: shop
+--
+
+ALTER TABLE ONLY public."UserAccount"
+    ADD CONSTRAINT "UserAccount_pkey" PRIMARY KEY ("userId");
+
+
+--
+-- Name: address address_pkey; Type: CONSTRAINT; Schema: public; Owner: shop
+--
+
+ALTER TABLE ONLY public.address
+    ADD CONSTRAINT address_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: order_line order_line_pkey; Type: CONSTRAINT; Schema: public; Owner: shop
+--
+
+ALTER TABLE ONLY public.order_line
+    ADD CONSTRAINT order_line_pkey PRIMARY KEY ("orderId", "productId");
+
+
+--
+-- Name: product product_pkey; Type: CONSTRAINT; Schema: public; Owner: shop
+--
+
+ALTER TABLE ONLY public.product
+    ADD CONSTRAINT product_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: shop_order shop_order_pkey; Type: CONSTRAINT; Schema: public; Owner: shop
+--
+
+ALTER TABLE ONLY public.shop_order
+    ADD CONSTRAINT shop_order_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: shop_order_user_idx; Type: INDEX; Schema: public; Owner: shop
+--
+
+CREATE INDEX shop_order_user_idx ON public.shop_order USING btree ("userId");
+
+
+--
+-- Name: UserAccount touch_user_account; Type: TRIGGER; Schema: public; Owner: shop
+--
+
+CREATE TRIGGER touch_user_account BEFORE UPDATE ON public."UserAccount" FOR EACH ROW EXECUTE FUNCTION public.touch_updated_at();
+
+
+--
+-- Name: address address_userId_fkey; Type: FK CONSTRAINT; Schema: public; Owner: shop
+--
+
+ALTER TABLE ONLY public.address
+    ADD CONSTRAINT "address_userId_fkey" FOREIGN KEY ("userId") REFERENCES public."UserAccount"("userId") ON DELETE CASCADE;
+
+
+--
+-- Name: order_line order_line_orderId_fkey; Type: FK CONSTRAINT; Schema: public; Owner: shop
+--
+
+ALTER TABLE ONLY public.order_line
+    ADD CONSTRAINT "order_line_orderId_fkey" FOREIGN KEY ("orderId") REFERENCES public.shop_order(id) ON UPDATE CASCADE ON DELETE CASCADE;
+
+
+--
+-- Name: order_line order_line_productId_fkey; Type: FK CONSTRAINT; Schema: public; Owner: shop
+--
+
+ALTER TABLE ONLY public.order_line
+    ADD CONSTRAINT "order_line_productId_fkey" FOREIGN KEY ("productId") REFERENCES public.product(id);
+
+
+--
+-- Name: shop_order shop_order_shippingAddressId_fkey; Type: FK CONSTRAINT; Schema: public; Owner: shop
+--
+
+ALTER TABLE ONLY public.shop_order
+    ADD CONSTRAINT "shop_order_shippingAddressId_fkey" FOREIGN KEY ("shippingAddressId") REFERENCES public.address(id) ON DELETE SET NULL;
+
+
+--
+-- Name: shop_order shop_order_userId_fkey; Type: FK CONSTRAINT; Schema: public; Owner: shop
+--
+
+ALTER TABLE ONLY public.shop_order
+    ADD CONSTRAINT "shop_order_userId_fkey" FOREIGN KEY ("userId") REFERENCES public."UserAccount"("userId") ON DELETE RESTRICT;
+
+
+--
+-- PostgreSQL database dump complete
+--
+